@@ -36,7 +36,7 @@ use fc_rpc::{
 };
 use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
 use fp_storage::EthereumStorageSchema;
-use psc_runtime::common::{opaque::Block, AccountId, Balance, Hash, Index};
+use psc_runtime::common::{opaque::Block, AccountId, AssetId, Balance, Hash, Index};
 use sc_client_api::{
     backend::{Backend, StateBackend, StorageProvider},
     client::BlockchainEvents,
@@ -130,6 +130,7 @@ where
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
     C::Api: BlockBuilder<Block>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: pallet_assets_bridge_rpc::AssetsBridgeRuntimeApi<Block, AccountId, AssetId>,
     C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
     C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
     P: TransactionPool<Block = Block> + 'static,
@@ -139,6 +140,7 @@ where
         Eth, EthApiServer, EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, Net,
         NetApiServer, Web3, Web3ApiServer,
     };
+    use pallet_assets_bridge_rpc::{AssetsBridge, AssetsBridgeApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -161,6 +163,7 @@ where
 
     io.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
     io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    io.merge(AssetsBridge::new(client.clone()).into_rpc())?;
 
     io.merge(
         Eth::new(
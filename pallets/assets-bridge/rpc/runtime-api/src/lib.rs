@@ -0,0 +1,60 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for assets bridge pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_core::H160;
+use sp_std::vec::Vec;
+
+pub use pallet_assets_bridge::{AssetRegistration, PauseFlags};
+
+sp_api::decl_runtime_apis! {
+    /// The API to query the mappings and registrations of assets bridge.
+    ///
+    /// Since version 2 the paused directions are returned as `PauseFlags` instead of a bool.
+    #[api_version(2)]
+    pub trait AssetsBridgeApi<AccountId, AssetId> where
+        AccountId: Codec,
+        AssetId: Codec,
+    {
+        /// The evm address bound to the substrate account.
+        fn evm_account(account: AccountId) -> Option<H160>;
+
+        /// The substrate account bound to the evm address.
+        fn sub_account(evm_address: H160) -> Option<AccountId>;
+
+        /// The erc20 contract registered for the asset.
+        fn erc20(asset_id: AssetId) -> Option<H160>;
+
+        /// The asset registered for the erc20 contract.
+        fn asset_id(erc20: H160) -> Option<AssetId>;
+
+        /// The paused directions of the asset.
+        fn pause_flags(asset_id: AssetId) -> PauseFlags;
+
+        /// All the registered assets.
+        fn registrations() -> Vec<AssetRegistration<AssetId>>;
+
+        /// The assets in emergency.
+        fn paused_assets() -> Vec<AssetId>;
+
+        /// The assets can back foreign chain.
+        fn back_foreign_assets() -> Vec<AssetId>;
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the assets bridge pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H160;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_assets_bridge_rpc_runtime_api::{
+    AssetRegistration, AssetsBridgeApi as AssetsBridgeRuntimeApi, PauseFlags,
+};
+
+#[rpc(client, server, namespace = "assets_bridge")]
+pub trait AssetsBridgeApi<BlockHash, AccountId, AssetId> {
+    /// The evm address bound to the substrate account.
+    #[method(name = "evmAccount")]
+    fn evm_account(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Option<H160>>;
+
+    /// The substrate account bound to the evm address.
+    #[method(name = "subAccount")]
+    fn sub_account(&self, evm_address: H160, at: Option<BlockHash>)
+        -> RpcResult<Option<AccountId>>;
+
+    /// The erc20 contract registered for the asset.
+    #[method(name = "erc20")]
+    fn erc20(&self, asset_id: AssetId, at: Option<BlockHash>) -> RpcResult<Option<H160>>;
+
+    /// The asset registered for the erc20 contract.
+    #[method(name = "assetId")]
+    fn asset_id(&self, erc20: H160, at: Option<BlockHash>) -> RpcResult<Option<AssetId>>;
+
+    /// The paused directions of the asset.
+    #[method(name = "pauseFlags")]
+    fn pause_flags(&self, asset_id: AssetId, at: Option<BlockHash>) -> RpcResult<PauseFlags>;
+
+    /// All the registered assets.
+    #[method(name = "registrations")]
+    fn registrations(&self, at: Option<BlockHash>) -> RpcResult<Vec<AssetRegistration<AssetId>>>;
+
+    /// The assets in emergency.
+    #[method(name = "pausedAssets")]
+    fn paused_assets(&self, at: Option<BlockHash>) -> RpcResult<Vec<AssetId>>;
+
+    /// The assets can back foreign chain.
+    #[method(name = "backForeignAssets")]
+    fn back_foreign_assets(&self, at: Option<BlockHash>) -> RpcResult<Vec<AssetId>>;
+}
+
+/// Provides RPC methods to query the assets bridge.
+pub struct AssetsBridge<C, B> {
+    client: Arc<C>,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B> AssetsBridge<C, B> {
+    /// Creates a new instance of the AssetsBridge Rpc helper.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call to runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+fn runtime_error(e: impl std::fmt::Display) -> jsonrpsee::core::Error {
+    CallError::Custom(ErrorObject::owned(
+        Error::RuntimeError.into(),
+        "Unable to query assets bridge.",
+        Some(e.to_string()),
+    ))
+    .into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, AssetId>
+    AssetsBridgeApiServer<<Block as BlockT>::Hash, AccountId, AssetId> for AssetsBridge<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: AssetsBridgeRuntimeApi<Block, AccountId, AssetId>,
+    AccountId: Codec + Send + Sync + 'static,
+    AssetId: Codec + Send + Sync + 'static,
+{
+    fn evm_account(
+        &self,
+        account: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<H160>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.evm_account(&at, account).map_err(runtime_error)
+    }
+
+    fn sub_account(
+        &self,
+        evm_address: H160,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AccountId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.sub_account(&at, evm_address).map_err(runtime_error)
+    }
+
+    fn erc20(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<H160>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.erc20(&at, asset_id).map_err(runtime_error)
+    }
+
+    fn asset_id(
+        &self,
+        erc20: H160,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AssetId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.asset_id(&at, erc20).map_err(runtime_error)
+    }
+
+    fn pause_flags(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<PauseFlags> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.pause_flags(&at, asset_id).map_err(runtime_error)
+    }
+
+    fn registrations(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<AssetRegistration<AssetId>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.registrations(&at).map_err(runtime_error)
+    }
+
+    fn paused_assets(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<AssetId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.paused_assets(&at).map_err(runtime_error)
+    }
+
+    fn back_foreign_assets(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<AssetId>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.back_foreign_assets(&at).map_err(runtime_error)
+    }
+}
@@ -15,50 +15,213 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+//! A minimal Solidity ABI encoder and decoder for the erc20 calls of the bridge, also used by the
+//! precompiles of the runtime.
+
 use super::*;
 
-pub fn mint_into_encode(account: H160, amount: u128) -> Vec<u8> {
-    // signature ++ account ++ amount
-    let length = 16 + 20 + 32;
-    let mut v = Vec::with_capacity(length);
+/// The selector of `Error(string)`, the revert reason of `require` and `revert`.
+pub const ERROR_STRING_SELECTOR: [u8; 4] = [8u8, 195, 121, 160];
+
+/// The abi values supported by the bridge.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum Token {
+    Address(H160),
+    Uint(U256),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    String(Vec<u8>),
+}
 
-    // bytes4(keccak256(bytes("mint_into(address,uint256)"))
-    // 0xefe51695
-    let sig_mint = [239u8, 229, 22, 149];
+impl Token {
+    fn is_dynamic(&self) -> bool {
+        matches!(self, Token::Bytes(_) | Token::String(_))
+    }
+}
 
-    // first 16-bytes
-    v.extend_from_slice(&sig_mint[..]);
-    v.extend_from_slice(&[0u8; 12][..]);
+/// The abi types of the decoded values.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum ParamType {
+    Address,
+    Uint,
+    Bool,
+    Bytes,
+    String,
+}
 
-    // second 20-bytes
-    v.extend_from_slice(&account[..]);
+/// bytes4(keccak256(bytes(signature)))
+pub fn selector(signature: &[u8]) -> [u8; 4] {
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&keccak_256(signature)[0..4]);
+    selector
+}
 
-    // third 32-bytes
-    v.extend_from_slice(&[0u8; 16][..]);
-    v.extend_from_slice(&amount.to_be_bytes()[..]);
+/// Encode `tokens` as the arguments of a function, i.e. head ++ tail.
+pub fn encode(tokens: &[Token]) -> Vec<u8> {
+    let heads_len = 32 * tokens.len();
+    let mut heads = Vec::with_capacity(heads_len);
+    let mut tails = Vec::new();
+
+    for token in tokens {
+        match token {
+            Token::Address(address) => {
+                heads.extend_from_slice(&[0u8; 12][..]);
+                heads.extend_from_slice(&address[..]);
+            },
+            Token::Uint(value) => extend_with_word(&mut heads, *value),
+            Token::Bool(value) => extend_with_word(&mut heads, U256::from(*value as u8)),
+            Token::Bytes(data) | Token::String(data) => {
+                extend_with_word(&mut heads, U256::from(heads_len + tails.len()));
+                extend_with_bytes(&mut tails, data);
+            },
+        }
+    }
+
+    heads.extend(tails);
+    heads
+}
 
+/// Encode the call of the function `signature` with `tokens`, i.e. selector ++ arguments.
+pub fn encode_call(signature: &[u8], tokens: &[Token]) -> Vec<u8> {
+    let mut v = selector(signature).to_vec();
+    v.extend(encode(tokens));
     v
 }
 
+/// Decode `data` as the values of `types`, None if `data` is malformed.
+pub fn decode(types: &[ParamType], data: &[u8]) -> Option<Vec<Token>> {
+    let mut tokens = Vec::with_capacity(types.len());
+
+    for (i, param) in types.iter().enumerate() {
+        let word = read_word(data, i.checked_mul(32)?)?;
+        let token = match param {
+            ParamType::Address => {
+                if word[0..12].iter().any(|b| *b != 0) {
+                    return None;
+                }
+                Token::Address(H160::from_slice(&word[12..]))
+            },
+            ParamType::Uint => Token::Uint(U256::from_big_endian(word)),
+            ParamType::Bool => match U256::from_big_endian(word) {
+                v if v.is_zero() => Token::Bool(false),
+                v if v == U256::one() => Token::Bool(true),
+                _ => return None,
+            },
+            ParamType::Bytes => Token::Bytes(read_bytes(data, word)?),
+            ParamType::String => Token::String(read_bytes(data, word)?),
+        };
+        tokens.push(token);
+    }
+
+    Some(tokens)
+}
+
+/// Decode the `bool` returned by a function.
+pub fn decode_bool(data: &[u8]) -> Option<bool> {
+    match decode(&[ParamType::Bool], data)?.pop()? {
+        Token::Bool(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// Decode the `uint256` returned by a function.
+pub fn decode_uint(data: &[u8]) -> Option<U256> {
+    match decode(&[ParamType::Uint], data)?.pop()? {
+        Token::Uint(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// Decode the reason of the `Error(string)` revert, None if it's not an `Error(string)`.
+pub fn decode_revert_reason(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() < 4 || data[0..4] != ERROR_STRING_SELECTOR {
+        return None;
+    }
+
+    match decode(&[ParamType::String], &data[4..])?.pop()? {
+        Token::String(reason) => Some(reason),
+        _ => None,
+    }
+}
+
+pub fn mint_into_encode(account: H160, amount: u128) -> Vec<u8> {
+    encode_call(
+        b"mint_into(address,uint256)",
+        &[Token::Address(account), Token::Uint(amount.into())],
+    )
+}
+
 pub fn burn_from_encode(account: H160, amount: u128) -> Vec<u8> {
-    // signature ++ account ++ amount
-    let length = 16 + 20 + 32;
-    let mut v = Vec::with_capacity(length);
+    encode_call(
+        b"burn_from(address,uint256)",
+        &[Token::Address(account), Token::Uint(amount.into())],
+    )
+}
 
-    // bytes4(keccak256(bytes("burn_from(address,uint256)"))
-    // 0x0f536f84
-    let sig_burn = [15u8, 83, 111, 132];
+pub fn transfer_encode(to: H160, amount: u128) -> Vec<u8> {
+    encode_call(b"transfer(address,uint256)", &[Token::Address(to), Token::Uint(amount.into())])
+}
 
-    // first 16-bytes
-    v.extend_from_slice(&sig_burn[..]);
-    v.extend_from_slice(&[0u8; 12][..]);
+pub fn transfer_from_encode(from: H160, to: H160, amount: u128) -> Vec<u8> {
+    encode_call(
+        b"transferFrom(address,address,uint256)",
+        &[Token::Address(from), Token::Address(to), Token::Uint(amount.into())],
+    )
+}
 
-    // second 20-bytes
-    v.extend_from_slice(&account[..]);
+pub fn decimals_encode() -> Vec<u8> {
+    encode_call(b"decimals()", &[])
+}
 
-    // third 32-bytes
-    v.extend_from_slice(&[0u8; 16][..]);
-    v.extend_from_slice(&amount.to_be_bytes()[..]);
+pub fn total_supply_encode() -> Vec<u8> {
+    encode_call(b"totalSupply()", &[])
+}
 
-    v
+pub fn balance_of_encode(account: H160) -> Vec<u8> {
+    encode_call(b"balanceOf(address)", &[Token::Address(account)])
+}
+
+pub fn erc20_constructor_encode(name: &[u8], symbol: &[u8], decimals: u8) -> Vec<u8> {
+    // constructor(string name_, string symbol_, uint8 decimals_)
+    encode(&[
+        Token::String(name.to_vec()),
+        Token::String(symbol.to_vec()),
+        Token::Uint(decimals.into()),
+    ])
+}
+
+fn padded_len(len: usize) -> usize {
+    (len + 31) / 32 * 32
+}
+
+fn extend_with_word(v: &mut Vec<u8>, word: U256) {
+    let mut buf = [0u8; 32];
+    word.to_big_endian(&mut buf);
+    v.extend_from_slice(&buf[..]);
+}
+
+fn extend_with_bytes(v: &mut Vec<u8>, data: &[u8]) {
+    extend_with_word(v, U256::from(data.len()));
+    v.extend_from_slice(data);
+    v.resize(v.len() + padded_len(data.len()) - data.len(), 0);
+}
+
+fn read_word(data: &[u8], offset: usize) -> Option<&[u8]> {
+    data.get(offset..offset.checked_add(32)?)
+}
+
+fn read_usize(word: &[u8]) -> Option<usize> {
+    let value = U256::from_big_endian(word);
+    if value > U256::from(u32::MAX) {
+        return None;
+    }
+    Some(value.low_u32() as usize)
+}
+
+/// Read the `bytes` or `string` at the offset of the head `word`.
+fn read_bytes(data: &[u8], word: &[u8]) -> Option<Vec<u8>> {
+    let offset = read_usize(word)?;
+    let len = read_usize(read_word(data, offset)?)?;
+    let start = offset.checked_add(32)?;
+    data.get(start..start.checked_add(len)?).map(|bytes| bytes.to_vec())
 }
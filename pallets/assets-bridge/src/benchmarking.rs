@@ -0,0 +1,538 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Assets bridge pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::fungibles::Mutate;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, TrailingZeroInput};
+use sp_std::vec;
+use xcm::latest::{Junction::Parachain, Junctions::X1};
+
+use crate::Pallet as AssetsBridge;
+
+// The erc20 address of the registered asset.
+const ERC20: [u8; 20] = [2u8; 20];
+// The runtime code of the erc20, which makes the storage and log footprint of a token transfer
+// for any call: credits the first argument and debits the caller by the second argument in the
+// balances keyed by `keccak256(address)`, emits `Transfer(caller, to, amount)` and returns true.
+const ERC20_RUNTIME_CODE: [u8; 95] = [
+    0x60, 0x04, 0x35, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0x20, 0x80, 0x54, 0x60, 0x24, 0x35,
+    0x01, 0x90, 0x55, 0x33, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0x20, 0x80, 0x54, 0x60, 0x24,
+    0x35, 0x90, 0x03, 0x90, 0x55, 0x60, 0x24, 0x35, 0x60, 0x00, 0x52, 0x60, 0x04, 0x35, 0x33, 0x7f,
+    0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+    0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+    0x60, 0x20, 0x60, 0x00, 0xa3, 0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+];
+// A creation bytecode returns the `ERC20_RUNTIME_CODE`.
+fn erc20_code() -> Vec<u8> {
+    let mut code = vec![0x60, 0x5f, 0x60, 0x0c, 0x60, 0x00, 0x39, 0x60, 0x5f, 0x60, 0x00, 0xf3];
+    code.extend_from_slice(&ERC20_RUNTIME_CODE);
+    code
+}
+
+fn secret_key(seed: &[u8]) -> libsecp256k1::SecretKey {
+    libsecp256k1::SecretKey::parse(&keccak_256(seed)).unwrap()
+}
+
+fn eth_address(secret: &libsecp256k1::SecretKey) -> H160 {
+    let public = libsecp256k1::PublicKey::from_secret_key(secret);
+    H160::from_slice(&keccak_256(&public.serialize()[1..65])[12..])
+}
+
+fn eth_sign(secret: &libsecp256k1::SecretKey, what: &[u8]) -> EcdsaSignature {
+    eth_sign_message(secret, &to_ascii_hex(what)[..])
+}
+
+fn eth_sign_message(secret: &libsecp256k1::SecretKey, message: &[u8]) -> EcdsaSignature {
+    let msg = keccak_256(&ethereum_signable_message(message, &[][..]));
+    let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(&msg), secret);
+    let mut r = [0u8; 65];
+    r[0..64].copy_from_slice(&sig.serialize()[..]);
+    r[64] = recovery_id.serialize();
+    EcdsaSignature::from_raw(r)
+}
+
+fn eth_sign_typed<T: Config>(
+    secret: &libsecp256k1::SecretKey,
+    who: &T::AccountId,
+) -> EcdsaSignature {
+    let mut what = b"\x19\x01".to_vec();
+    what.extend_from_slice(&AssetsBridge::<T>::eip712_domain_separator()[..]);
+    what.extend_from_slice(&who.using_encoded(eip712_claim_hash)[..]);
+    let msg = keccak_256(&what);
+    let (sig, recovery_id) = libsecp256k1::sign(&libsecp256k1::Message::parse(&msg), secret);
+    let mut r = [0u8; 65];
+    r[0..64].copy_from_slice(&sig.serialize()[..]);
+    r[64] = recovery_id.serialize();
+    EcdsaSignature::from_raw(r)
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+fn funded_caller<T: Config>() -> T::AccountId {
+    let caller: T::AccountId = whitelisted_caller();
+    <T as pallet_assets::Config>::Currency::make_free_balance_be(
+        &caller,
+        ReserveBalanceOf::<T>::max_value() / 2u32.into(),
+    );
+    caller
+}
+
+fn funded_unclaimed<T: Config>(eth_address: H160) -> T::AccountId {
+    let unclaimed = T::UnclaimedAddressMapping::into_account_id(eth_address);
+    <T as pallet_evm::Config>::Currency::make_free_balance_be(
+        &unclaimed,
+        BalanceOf::<T>::max_value() / 4u32.into(),
+    );
+    unclaimed
+}
+
+fn mapped_caller<T: Config>() -> (T::AccountId, H160) {
+    let caller = funded_caller::<T>();
+    let evm_account = eth_address(&secret_key(b"caller"));
+    SubAccounts::<T>::insert(evm_account, &caller);
+    EvmAccounts::<T>::insert(&caller, evm_account);
+    (caller, evm_account)
+}
+
+fn registered_asset<T: Config>(owner: &T::AccountId) -> (T::AssetId, H160) {
+    let asset_id = T::AssetId::default();
+    let erc20 = H160::from_slice(&ERC20);
+    assert!(pallet_assets::Pallet::<T>::force_create(
+        RawOrigin::Root.into(),
+        asset_id,
+        T::Lookup::unlookup(owner.clone()),
+        true,
+        1u32.into(),
+    )
+    .is_ok());
+    Erc20s::<T>::insert(asset_id, erc20);
+    AssetIds::<T>::insert(erc20, asset_id);
+    pallet_evm::AccountCodes::<T>::insert(erc20, ERC20_RUNTIME_CODE.to_vec());
+    (asset_id, erc20)
+}
+
+// Register `n` assets without their erc20 code, which are paused and unpaused all together.
+fn registered_assets<T: Config>(n: u32) {
+    for i in 0..n {
+        let asset_id = T::AssetId::decode(&mut TrailingZeroInput::new(&i.encode())).unwrap();
+        let erc20 = H160::from_low_u64_be(i as u64 + 1);
+        Erc20s::<T>::insert(asset_id, erc20);
+        AssetIds::<T>::insert(erc20, asset_id);
+    }
+}
+
+// Limit the volumes to make the worst case.
+fn volume_limited<T: Config>(asset_id: T::AssetId, direction: Direction) {
+    let limit = VolumeLimit {
+        window: 100u32.into(),
+        total: Some(T::Balance::max_value()),
+        per_account: Some(T::Balance::max_value()),
+    };
+    VolumeLimits::<T>::insert(asset_id, direction, limit);
+}
+
+// Lock and release the erc20 tokens by the escrow to make the worst case.
+fn lock_released<T: Config>(asset_id: T::AssetId) {
+    BridgeModes::<T>::insert(asset_id, BridgeMode::LockRelease);
+    EscrowBalances::<T>::insert(asset_id, u128::MAX / 2);
+}
+
+fn pending_back_foreign<T: Config>(who: &T::AccountId, asset_id: T::AssetId) -> u64 {
+    let nonce = BackForeignNonce::<T>::get();
+    let dest = BackForeignDest::try_from(who.encode()).unwrap();
+    AssetsBridge::<T>::queue_back_foreign(who, asset_id, 1_000u32.into(), dest);
+    nonce
+}
+
+// Note the volumes of the pending back foreign transfer, which are removed when it is cancelled.
+fn back_foreign_noted<T: Config>(who: &T::AccountId, asset_id: T::AssetId) {
+    volume_limited::<T>(asset_id, Direction::BackForeign);
+    let noted: (T::BlockNumber, T::Balance) =
+        (frame_system::Pallet::<T>::block_number(), 1_000u32.into());
+    TotalVolumes::<T>::insert(asset_id, Direction::BackForeign, noted);
+    AccountVolumes::<T>::insert((asset_id, Direction::BackForeign, who), noted);
+}
+
+fn set_relayer<T: Config>() -> T::AccountId {
+    let relayer: T::AccountId = account("relayer", 0, 0);
+    Relayer::<T>::put(&relayer);
+    relayer
+}
+
+benchmarks! {
+    where_clause {
+        where DispatchError: From<<<T as pallet_evm::Config>::Runner as pallet_evm::Runner<T>>::Error>
+    }
+
+    claim_account {
+        let caller = funded_caller::<T>();
+        let secret = secret_key(b"caller");
+        let eth_address = eth_address(&secret);
+        let eth_signature = eth_sign(&secret, &caller.encode());
+        let unclaimed = funded_unclaimed::<T>(eth_address);
+    }: _(RawOrigin::Signed(caller.clone()), eth_address, eth_signature)
+    verify {
+        assert_eq!(EvmAccounts::<T>::get(&caller), Some(eth_address));
+        assert!(<T as pallet_evm::Config>::Currency::free_balance(&unclaimed).is_zero());
+        assert_last_event::<T>(Event::ClaimAccount(caller, eth_address).into());
+    }
+
+    claim_account_eip712 {
+        let caller = funded_caller::<T>();
+        let secret = secret_key(b"caller");
+        let eth_address = eth_address(&secret);
+        let eth_signature = eth_sign_typed::<T>(&secret, &caller);
+        let unclaimed = funded_unclaimed::<T>(eth_address);
+    }: _(RawOrigin::Signed(caller.clone()), eth_address, eth_signature)
+    verify {
+        assert_eq!(EvmAccounts::<T>::get(&caller), Some(eth_address));
+        assert!(<T as pallet_evm::Config>::Currency::free_balance(&unclaimed).is_zero());
+        assert_last_event::<T>(Event::ClaimAccount(caller, eth_address).into());
+    }
+
+    dissolve {
+        let (caller, _) = mapped_caller::<T>();
+    }: _(RawOrigin::Signed(caller.clone()))
+    verify {
+        assert!(!EvmAccounts::<T>::contains_key(&caller));
+        assert_last_event::<T>(Event::Dissolve(caller).into());
+    }
+
+    deposit {
+        let (caller, evm_account) = mapped_caller::<T>();
+        let (asset_id, erc20) = registered_asset::<T>(&caller);
+        volume_limited::<T>(asset_id, Direction::Deposit);
+        lock_released::<T>(asset_id);
+        let amount: T::Balance = 1_000u32.into();
+        pallet_assets::Pallet::<T>::mint_into(asset_id, &caller, amount)?;
+    }: _(RawOrigin::Signed(caller.clone()), asset_id, amount)
+    verify {
+        assert_last_event::<T>(
+            Event::DepositExecuted(asset_id, caller, evm_account, amount, erc20).into()
+        );
+    }
+
+    withdraw {
+        let (caller, evm_account) = mapped_caller::<T>();
+        let (asset_id, erc20) = registered_asset::<T>(&caller);
+        volume_limited::<T>(asset_id, Direction::Withdraw);
+        lock_released::<T>(asset_id);
+        let amount: T::Balance = 1_000u32.into();
+    }: _(RawOrigin::Signed(caller.clone()), asset_id, amount)
+    verify {
+        assert_last_event::<T>(
+            Event::WithdrawExecuted(asset_id, caller, evm_account, amount, erc20).into()
+        );
+    }
+
+    deposit_to {
+        let caller = funded_caller::<T>();
+        let recipient = eth_address(&secret_key(b"recipient"));
+        let (asset_id, erc20) = registered_asset::<T>(&caller);
+        volume_limited::<T>(asset_id, Direction::Deposit);
+        lock_released::<T>(asset_id);
+        let amount: T::Balance = 1_000u32.into();
+        pallet_assets::Pallet::<T>::mint_into(asset_id, &caller, amount)?;
+    }: _(RawOrigin::Signed(caller.clone()), asset_id, amount, recipient)
+    verify {
+        assert_last_event::<T>(
+            Event::DepositExecuted(asset_id, caller, recipient, amount, erc20).into()
+        );
+    }
+
+    withdraw_to {
+        let (caller, evm_account) = mapped_caller::<T>();
+        let dest: T::AccountId = account("dest", 0, 0);
+        let (asset_id, erc20) = registered_asset::<T>(&caller);
+        volume_limited::<T>(asset_id, Direction::Withdraw);
+        lock_released::<T>(asset_id);
+        let amount: T::Balance = 1_000u32.into();
+    }: _(RawOrigin::Signed(caller), asset_id, amount, T::Lookup::unlookup(dest.clone()))
+    verify {
+        assert_last_event::<T>(
+            Event::WithdrawExecuted(asset_id, dest, evm_account, amount, erc20).into()
+        );
+    }
+
+    teleport {
+        let (caller, _) = mapped_caller::<T>();
+        let amount: BalanceOf<T> = 1_000u32.into();
+        <T as pallet_evm::Config>::Currency::make_free_balance_be(
+            &caller,
+            BalanceOf::<T>::max_value() / 2u32.into(),
+        );
+        let action = ActionType::Direct(H160::from_low_u64_be(1));
+    }: _(RawOrigin::Signed(caller.clone()), amount, action.clone())
+    verify {
+        assert_last_event::<T>(Event::Teleport(caller, amount, action).into());
+    }
+
+    register {
+        let origin = T::RegisterOrigin::successful_origin();
+        let asset_id = T::AssetId::default();
+        let erc20 = H160::from_slice(&ERC20);
+        pallet_evm::AccountCodes::<T>::insert(erc20, ERC20_RUNTIME_CODE.to_vec());
+    }: _<T::RuntimeOrigin>(origin, asset_id, erc20)
+    verify {
+        assert_eq!(Erc20s::<T>::get(asset_id), Some(erc20));
+        assert_eq!(NetBridged::<T>::get(asset_id), 1);
+        assert_last_event::<T>(Event::Register(asset_id, erc20).into());
+    }
+
+    register_with_decimals {
+        let origin = T::RegisterOrigin::successful_origin();
+        let asset_id = T::AssetId::default();
+        let erc20 = H160::from_slice(&ERC20);
+        // the `decimals()` of the `ERC20_RUNTIME_CODE` is 1
+        pallet_evm::AccountCodes::<T>::insert(erc20, ERC20_RUNTIME_CODE.to_vec());
+    }: _<T::RuntimeOrigin>(origin, asset_id, erc20, 1)
+    verify {
+        assert_eq!(Decimals::<T>::get(asset_id), Some((0, 1)));
+        assert_last_event::<T>(Event::Register(asset_id, erc20).into());
+    }
+
+    register_lock_release {
+        let origin = T::RegisterOrigin::successful_origin();
+        let asset_id = T::AssetId::default();
+        let erc20 = H160::from_slice(&ERC20);
+        pallet_evm::AccountCodes::<T>::insert(erc20, ERC20_RUNTIME_CODE.to_vec());
+    }: _<T::RuntimeOrigin>(origin, asset_id, erc20, 1)
+    verify {
+        assert_eq!(BridgeModes::<T>::get(asset_id), BridgeMode::LockRelease);
+        assert_last_event::<T>(Event::Register(asset_id, erc20).into());
+    }
+
+    register_and_deploy {
+        let origin = T::RegisterOrigin::successful_origin();
+        let owner = funded_caller::<T>();
+        let asset_id = T::AssetId::default();
+        pallet_assets::Pallet::<T>::force_create(
+            RawOrigin::Root.into(),
+            asset_id,
+            T::Lookup::unlookup(owner),
+            true,
+            1u32.into(),
+        )?;
+        pallet_assets::Pallet::<T>::force_set_metadata(
+            RawOrigin::Root.into(),
+            asset_id,
+            b"Polkadot".to_vec(),
+            b"DOT".to_vec(),
+            10,
+            false,
+        )?;
+        Erc20Code::<T>::put(BoundedVec::truncate_from(erc20_code()));
+    }: _<T::RuntimeOrigin>(origin, asset_id)
+    verify {
+        assert!(Erc20s::<T>::contains_key(asset_id));
+    }
+
+    pause {
+        let n in 1 .. 100;
+        let origin = T::PauseOrigin::successful_origin();
+        registered_assets::<T>(n);
+    }: _<T::RuntimeOrigin>(origin, None, None)
+    verify {
+        assert_eq!(AssetsBridge::<T>::paused_assets().len(), n as usize);
+        assert_last_event::<T>(Event::PausedAll(None).into());
+    }
+
+    unpause {
+        let n in 1 .. 100;
+        let origin = T::PauseOrigin::successful_origin();
+        registered_assets::<T>(n);
+        AssetsBridge::<T>::pause(origin.clone(), None, None)?;
+    }: _<T::RuntimeOrigin>(origin, None, None)
+    verify {
+        assert!(AssetsBridge::<T>::paused_assets().is_empty());
+        assert_last_event::<T>(Event::UnPausedAll(None).into());
+    }
+
+    back_foreign {
+        let origin = T::BackForeignOrigin::successful_origin();
+        let asset_id = T::AssetId::default();
+    }: _<T::RuntimeOrigin>(origin, asset_id, false)
+    verify {
+        assert_last_event::<T>(Event::BackForeign(asset_id, false).into());
+    }
+
+    set_volume_limit {
+        let origin = T::RegisterOrigin::successful_origin();
+        let asset_id = T::AssetId::default();
+        let limit = VolumeLimit {
+            window: 100u32.into(),
+            total: Some(1_000u32.into()),
+            per_account: Some(100u32.into()),
+        };
+    }: _<T::RuntimeOrigin>(origin, asset_id, Direction::Deposit, Some(limit.clone()))
+    verify {
+        assert_eq!(VolumeLimits::<T>::get(asset_id, Direction::Deposit), Some(limit));
+    }
+
+    set_gas_limit {
+        let origin = T::RegisterOrigin::successful_origin();
+        let (asset_id, _) = registered_asset::<T>(&funded_caller::<T>());
+    }: _<T::RuntimeOrigin>(origin, asset_id, Some(MAX_GAS_LIMIT))
+    verify {
+        assert_eq!(AssetsBridge::<T>::gas_limit(asset_id), MAX_GAS_LIMIT);
+        assert_last_event::<T>(Event::SetGasLimit(asset_id, Some(MAX_GAS_LIMIT)).into());
+    }
+
+    set_admin {
+        let origin = T::AdminOrigin::successful_origin();
+        let new_admin: T::AccountId = whitelisted_caller();
+    }: _<T::RuntimeOrigin>(origin, T::Lookup::unlookup(new_admin.clone()))
+    verify {
+        assert_last_event::<T>(Event::SetAdmin(new_admin).into());
+    }
+
+    force_unregister {
+        let origin = T::AdminOrigin::successful_origin();
+        let (asset_id, erc20) = registered_asset::<T>(&funded_caller::<T>());
+        AssetsBridge::<T>::pause(T::PauseOrigin::successful_origin(), Some(asset_id), None)?;
+    }: _<T::RuntimeOrigin>(origin, asset_id)
+    verify {
+        assert!(!Erc20s::<T>::contains_key(asset_id));
+        assert_last_event::<T>(Event::ForceUnRegister(asset_id, erc20).into());
+    }
+
+    set_erc20_code {
+        let c in 0 .. T::MaxErc20CodeSize::get();
+        let origin = T::AdminOrigin::successful_origin();
+        let code = vec![0u8; c as usize];
+    }: _<T::RuntimeOrigin>(origin, code.clone())
+    verify {
+        assert_eq!(Erc20Code::<T>::get().into_inner(), code);
+    }
+
+    withdraw_signed {
+        let (caller, evm_account) = mapped_caller::<T>();
+        let (asset_id, erc20) = registered_asset::<T>(&caller);
+        volume_limited::<T>(asset_id, Direction::Withdraw);
+        lock_released::<T>(asset_id);
+        let amount: T::Balance = 1_000u32.into();
+        let message = AssetsBridge::<T>::withdraw_message(asset_id, amount, 0);
+        let eth_signature = eth_sign_message(&secret_key(b"caller"), &message);
+    }: _(RawOrigin::None, evm_account, asset_id, amount, eth_signature)
+    verify {
+        assert_eq!(WithdrawNonces::<T>::get(evm_account), 1);
+        assert_last_event::<T>(
+            Event::WithdrawExecuted(asset_id, caller, evm_account, amount, erc20).into()
+        );
+    }
+
+    set_relayer {
+        let origin = T::BackForeignOrigin::successful_origin();
+        let relayer: T::AccountId = account("relayer", 0, 0);
+    }: _<T::RuntimeOrigin>(origin, T::Lookup::unlookup(relayer.clone()))
+    verify {
+        assert_last_event::<T>(Event::SetRelayer(relayer).into());
+    }
+
+    confirm_back_foreign {
+        let (caller, _) = mapped_caller::<T>();
+        let (asset_id, _) = registered_asset::<T>(&caller);
+        let nonce = pending_back_foreign::<T>(&caller, asset_id);
+        let relayer = set_relayer::<T>();
+    }: _(RawOrigin::Signed(relayer), nonce)
+    verify {
+        assert_eq!(
+            PendingBackForeign::<T>::get(nonce).unwrap().status,
+            BackForeignStatus::Confirmed
+        );
+        assert_last_event::<T>(Event::BackForeignConfirmed(nonce).into());
+    }
+
+    refund_back_foreign {
+        let (caller, _) = mapped_caller::<T>();
+        let (asset_id, _) = registered_asset::<T>(&caller);
+        let nonce = pending_back_foreign::<T>(&caller, asset_id);
+        back_foreign_noted::<T>(&caller, asset_id);
+        let relayer = set_relayer::<T>();
+    }: _(RawOrigin::Signed(relayer), nonce)
+    verify {
+        assert_eq!(PendingBackForeign::<T>::get(nonce).unwrap().status, BackForeignStatus::Refunded);
+        assert!(TotalVolumes::<T>::get(asset_id, Direction::BackForeign).1.is_zero());
+        assert_last_event::<T>(Event::BackForeignRefunded(nonce).into());
+    }
+
+    reclaim_back_foreign {
+        let (caller, _) = mapped_caller::<T>();
+        let (asset_id, _) = registered_asset::<T>(&caller);
+        let nonce = pending_back_foreign::<T>(&caller, asset_id);
+        back_foreign_noted::<T>(&caller, asset_id);
+        let deadline = PendingBackForeign::<T>::get(nonce).unwrap().deadline;
+        frame_system::Pallet::<T>::set_block_number(deadline);
+    }: _(RawOrigin::Signed(caller), nonce)
+    verify {
+        assert_eq!(PendingBackForeign::<T>::get(nonce).unwrap().status, BackForeignStatus::Reclaimed);
+        assert!(TotalVolumes::<T>::get(asset_id, Direction::BackForeign).1.is_zero());
+        assert_last_event::<T>(Event::BackForeignReclaimed(nonce).into());
+    }
+
+    set_reserve_location {
+        let origin = T::BackForeignOrigin::successful_origin();
+        let asset_id = T::AssetId::default();
+        let location = MultiLocation::new(1, X1(Parachain(1000)));
+    }: _<T::RuntimeOrigin>(origin, asset_id, Some(Box::new(location.clone().into())))
+    verify {
+        assert_eq!(ReserveLocations::<T>::get(asset_id), Some(location));
+    }
+
+    set_dest_format {
+        let origin = T::BackForeignOrigin::successful_origin();
+        let asset_id = T::AssetId::default();
+    }: _<T::RuntimeOrigin>(origin, asset_id, DestFormat::AccountKey20)
+    verify {
+        assert_eq!(DestFormats::<T>::get(asset_id), DestFormat::AccountKey20);
+    }
+
+    transfer_back_foreign {
+        let caller = funded_caller::<T>();
+        let (asset_id, _) = registered_asset::<T>(&caller);
+        volume_limited::<T>(asset_id, Direction::BackForeign);
+        BackForeign::<T>::insert(asset_id, ());
+        let amount: T::Balance = 1_000u32.into();
+        pallet_assets::Pallet::<T>::mint_into(asset_id, &caller, amount)?;
+        let dest = BackForeignDest::try_from(vec![1u8; 32]).unwrap();
+    }: _(RawOrigin::Signed(caller.clone()), asset_id, amount, dest.clone())
+    verify {
+        assert_last_event::<T>(Event::BackForeignQueued(0, caller, asset_id, amount, dest).into());
+    }
+
+    report_drift {
+        let caller = funded_caller::<T>();
+        let (asset_id, erc20) = registered_asset::<T>(&caller);
+        // PUSH1 1 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        pallet_evm::AccountCodes::<T>::insert(
+            erc20,
+            vec![0x60, 0x01, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3],
+        );
+    }: _(RawOrigin::None, asset_id)
+    verify {
+        assert_last_event::<T>(Event::Paused(asset_id, None).into());
+    }
+
+    impl_benchmark_test_suite!(AssetsBridge, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -24,6 +24,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod abi;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -31,20 +34,47 @@ mod tests;
 pub use abi::*;
 pub mod recover;
 pub use recover::*;
+pub mod weights;
+pub use weights::WeightInfo;
 
 use codec::Encode;
 use frame_support::{
+    dispatch::PostDispatchInfo,
     ensure,
     pallet_prelude::*,
-    traits::{Currency, ExistenceRequirement, IsType},
+    storage::{with_transaction, TransactionOutcome},
+    traits::{Currency, ExistenceRequirement, IsType, PalletInfoAccess, ReservableCurrency},
     transactional,
+    weights::Weight,
 };
-use sp_core::{ecdsa, H160, U256};
+use frame_system::offchain::{SendTransactionTypes, SubmitTransaction};
+use sp_core::{ecdsa, H160, H256, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
-use sp_runtime::traits::{StaticLookup, UniqueSaturatedInto, Zero};
-use sp_std::vec::Vec;
+use sp_runtime::{
+    traits::{Convert, One, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
+    ArithmeticError,
+};
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+use pallet_evm::{AddressMapping, ExitError, ExitReason, GasWeightMapping, Runner};
+use xcm::{
+    latest::{MultiLocation, SendXcm},
+    VersionedMultiLocation,
+};
 
-use pallet_evm::{AddressMapping, ExitReason, Runner};
+/// The default gas limit of the inner erc20 contract calls.
+pub const GAS_LIMIT: u64 = 3_000_000;
+/// The max gas limit of the inner erc20 contract calls set by `set_gas_limit`.
+pub const MAX_GAS_LIMIT: u64 = 15_000_000;
+/// The max length of the destination on the foreign chain of the back foreign transfers.
+pub const MAX_DEST_LEN: u32 = 64;
+/// The gas limit of the inner erc20 contract creations.
+pub const CREATE_GAS_LIMIT: u64 = 5_000_000;
+/// The max back foreign transfers finished in one block, their retention ends in the same block.
+pub const MAX_FINISHED_BACK_FOREIGN: u32 = 64;
+/// The dummy code set under the erc20 precompile addresses of the assets, it only reverts if
+/// executed as a contract.
+pub const PRECOMPILE_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
 
 pub type EcdsaSignature = ecdsa::Signature;
 pub type AddressMappingOf<T> = <T as pallet_evm::Config>::AddressMapping;
@@ -63,21 +93,254 @@ pub enum ActionType<AssetId> {
     BackForeign(AssetId),
 }
 
+/// The registration of an asset, queried by the runtime api.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetRegistration<AssetId> {
+    pub asset_id: AssetId,
+    pub erc20: H160,
+    /// The paused directions.
+    pub paused: PauseFlags,
+    pub back_foreign: bool,
+}
+
+/// The direction of the assets bridge transfers.
+#[derive(
+    Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub enum Direction {
+    /// substrate assets -> erc20 tokens
+    Deposit,
+    /// erc20 tokens -> substrate assets
+    Withdraw,
+    /// substrate assets -> foreign chain
+    BackForeign,
+}
+
+/// How the erc20 tokens of an asset are bridged.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Encode,
+    Decode,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+pub enum BridgeMode {
+    /// `deposit` mints and `withdraw` burns the erc20 tokens, the erc20 contract grants the
+    /// minting rights to `EvmCaller`.
+    #[default]
+    MintBurn,
+    /// `withdraw` locks the erc20 tokens into the escrow `EvmCaller` by `transferFrom`, and
+    /// `deposit` releases them by `transfer`, for the third-party erc20 contracts.
+    LockRelease,
+}
+
+/// The format of the destinations on the foreign chain of an asset.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Encode,
+    Decode,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+pub enum DestFormat {
+    /// A 32 bytes account, e.g. the substrate chains.
+    #[default]
+    AccountId32,
+    /// A 20 bytes account, e.g. the evm chains.
+    AccountKey20,
+    /// Any non-empty bytes checked by the relayer, can't be sent by xcm.
+    Raw,
+}
+
+/// The destination on the foreign chain of a back foreign transfer.
+pub type BackForeignDest = BoundedVec<u8, ConstU32<MAX_DEST_LEN>>;
+
+/// The status of a back foreign transfer.
+#[derive(
+    Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub enum BackForeignStatus {
+    /// The asset is burned, waiting for the relayer to pay on the foreign chain.
+    Pending,
+    /// The relayer has paid on the foreign chain.
+    Confirmed,
+    /// The relayer failed to pay, the asset is minted back.
+    Refunded,
+    /// The relayer didn't respond before the deadline, the asset is reclaimed by the owner.
+    Reclaimed,
+}
+
+/// A back foreign transfer tracked in `PendingBackForeign`, kept with its final status for
+/// `BackForeignRetention` blocks after it is finished.
+#[derive(
+    Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub struct BackForeignTransfer<AccountId, AssetId, Balance, BlockNumber> {
+    /// The owner of the burned asset.
+    pub who: AccountId,
+    pub asset_id: AssetId,
+    pub amount: Balance,
+    /// The destination on the foreign chain.
+    pub dest: BackForeignDest,
+    /// The owner can reclaim the pending transfer from this block.
+    pub deadline: BlockNumber,
+    pub status: BackForeignStatus,
+}
+
+/// The paused directions of one asset.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Encode,
+    Decode,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    MaxEncodedLen,
+    scale_info::TypeInfo,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PauseFlags {
+    pub deposit: bool,
+    pub withdraw: bool,
+    pub back_foreign: bool,
+}
+
+impl PauseFlags {
+    /// All the directions are paused.
+    pub fn all() -> Self {
+        Self { deposit: true, withdraw: true, back_foreign: true }
+    }
+
+    /// None of the directions is paused.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the `direction` is paused.
+    pub fn is_paused(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Deposit => self.deposit,
+            Direction::Withdraw => self.withdraw,
+            Direction::BackForeign => self.back_foreign,
+        }
+    }
+
+    /// Set the flag of `direction`, or all the flags if `direction` is None.
+    pub fn set(&mut self, direction: Option<Direction>, paused: bool) {
+        match direction {
+            Some(Direction::Deposit) => self.deposit = paused,
+            Some(Direction::Withdraw) => self.withdraw = paused,
+            Some(Direction::BackForeign) => self.back_foreign = paused,
+            None => *self = if paused { Self::all() } else { Self::default() },
+        }
+    }
+}
+
+/// The volume limit of one asset in one direction.
+#[derive(
+    Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
+)]
+pub struct VolumeLimit<Balance, BlockNumber> {
+    /// The number of blocks of one window, zero limits every single transfer.
+    pub window: BlockNumber,
+    /// The max volume of all the accounts within one window.
+    pub total: Option<Balance>,
+    /// The max volume of one account within one window.
+    pub per_account: Option<Balance>,
+}
+
+/// The evm address mapping resolving the claimed addresses into their `SubAccounts`, and the
+/// unclaimed ones by `Config::UnclaimedAddressMapping`.
+pub struct ClaimedAddressMapping<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> AddressMapping<T::AccountId> for ClaimedAddressMapping<T> {
+    fn into_account_id(address: H160) -> T::AccountId {
+        SubAccounts::<T>::get(address)
+            .unwrap_or_else(|| T::UnclaimedAddressMapping::into_account_id(address))
+    }
+}
+
+/// Ensure the origin is signed by the `Admin` of the pallet.
+pub struct EnsureAdmin<T>(sp_std::marker::PhantomData<T>);
+
+impl<T, O> EnsureOrigin<O> for EnsureAdmin<T>
+where
+    T: Config,
+    O: Into<Result<frame_system::RawOrigin<T::AccountId>, O>>
+        + From<frame_system::RawOrigin<T::AccountId>>,
+{
+    type Success = T::AccountId;
+
+    fn try_origin(o: O) -> Result<Self::Success, O> {
+        o.into().and_then(|o| match o {
+            frame_system::RawOrigin::Signed(who)
+                if Some(&who) == Pallet::<T>::admin_key().as_ref() =>
+                Ok(who),
+            r => Err(O::from(r)),
+        })
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> O {
+        let admin = Pallet::<T>::admin_key().expect("the admin key is set in the genesis; qed");
+        O::from(frame_system::RawOrigin::Signed(admin))
+    }
+}
+
+/// Remove the native erc20 allowances of the reaped accounts.
+///
+/// Each allowance was paid by the owner with the gas of an `approve`.
+impl<T: Config> frame_support::traits::OnKilledAccount<T::AccountId> for Pallet<T> {
+    fn on_killed_account(who: &T::AccountId) {
+        let _ = NativeAllowances::<T>::clear_prefix(who, u32::MAX, None);
+    }
+}
+
+/// Set the `PRECOMPILE_CODE` of every created asset, registered or not.
+impl<T: Config> pallet_assets::AssetsCallback<T::AssetId, T::AccountId> for Pallet<T> {
+    fn created(asset_id: T::AssetId, _owner: &T::AccountId) {
+        Self::set_precompile_code(asset_id);
+    }
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::traits::{fungibles::Mutate, ReservableCurrency};
+    use frame_support::traits::fungibles::{metadata::Inspect as MetadataInspect, Mutate};
     use frame_system::pallet_prelude::*;
 
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
-    #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + pallet_assets::Config + pallet_evm::Config {
+    pub trait Config:
+        frame_system::Config
+        + pallet_assets::Config
+        + pallet_evm::Config
+        + SendTransactionTypes<Call<Self>>
+    {
         /// The overarching event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// The assets-bridge's inner evm caller.
@@ -86,6 +349,52 @@ pub mod pallet {
         /// How much should be locked up in order to claim account.
         #[pallet::constant]
         type ClaimBond: Get<ReserveBalanceOf<Self>>;
+        /// How many fee-free `deposit`, `withdraw`, `deposit_to`, `withdraw_to` and `teleport`
+        /// an account can make within one `FreeCallsPeriod`, the exceeded calls will pay the fee.
+        #[pallet::constant]
+        type FreeCalls: Get<u32>;
+        /// The number of blocks of one fee-free calls period.
+        #[pallet::constant]
+        type FreeCallsPeriod: Get<Self::BlockNumber>;
+        /// The max size of the erc20 contract creation bytecode.
+        #[pallet::constant]
+        type MaxErc20CodeSize: Get<u32>;
+        /// The priority of the unsigned `withdraw_signed` and `report_drift` transactions.
+        #[pallet::constant]
+        type UnsignedPriority: Get<TransactionPriority>;
+        /// The number of blocks between the off-chain checks of the `NetBridged` of the assets,
+        /// zero disables the off-chain worker.
+        #[pallet::constant]
+        type DriftCheckInterval: Get<Self::BlockNumber>;
+        /// The number of blocks the relayer has to confirm or refund a back foreign transfer,
+        /// after which the owner can reclaim it.
+        #[pallet::constant]
+        type BackForeignTimeout: Get<Self::BlockNumber>;
+        /// The number of blocks the finished back foreign transfers are kept with their final
+        /// status.
+        #[pallet::constant]
+        type BackForeignRetention: Get<Self::BlockNumber>;
+        /// The xcm router sending the back foreign transfers of the reserve assets.
+        type XcmSender: SendXcm;
+        /// The location of the substrate accounts as the beneficiaries on the reserve chains.
+        type AccountIdToMultiLocation: Convert<Self::AccountId, MultiLocation>;
+        /// The address of the erc20 precompile of an asset, the `PRECOMPILE_CODE` is set under it
+        /// when the asset is created since solidity checks the code size before calling a
+        /// contract. Set `pallet_assets::Config::CallbackHandle` to this pallet to do so.
+        type AssetPrecompileAddress: Convert<Self::AssetId, H160>;
+        /// The address mapping of the unclaimed evm addresses, e.g. `HashedAddressMapping`.
+        type UnclaimedAddressMapping: AddressMapping<Self::AccountId>;
+        /// The origin registering the assets and setting their volume and gas limits.
+        type RegisterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// The origin pausing and unpausing the assets in emergency.
+        type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// The origin managing the back foreign assets, their reserve locations, destination
+        /// formats and the relayer.
+        type BackForeignOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// The origin setting the admin and the erc20 code, and force unregistering the assets.
+        type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     /// The Substrate Account for Evm Addresses
@@ -105,9 +414,11 @@ pub mod pallet {
     /// The Erc20 Contract Addresses for Asset Ids
     ///
     /// Erc20s: map AssetId => Option<H160>
+    ///
+    /// Counted to weigh pausing and unpausing all the assets.
     #[pallet::storage]
     #[pallet::getter(fn erc20s)]
-    pub type Erc20s<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, H160, OptionQuery>;
+    pub type Erc20s<T: Config> = CountedStorageMap<_, Twox64Concat, T::AssetId, H160, OptionQuery>;
 
     /// The Asset Ids for Erc20 Contract Addresses
     ///
@@ -116,22 +427,204 @@ pub mod pallet {
     #[pallet::getter(fn asset_ids)]
     pub type AssetIds<T: Config> = StorageMap<_, Twox64Concat, H160, T::AssetId, OptionQuery>;
 
+    /// The Bridge Modes of the registered Asset Ids, `MintBurn` by default
+    ///
+    /// BridgeModes: map AssetId => BridgeMode
+    #[pallet::storage]
+    #[pallet::getter(fn bridge_modes)]
+    pub type BridgeModes<T: Config> =
+        StorageMap<_, Twox64Concat, T::AssetId, BridgeMode, ValueQuery>;
+
+    /// The erc20 amounts locked in the escrow `EvmCaller` of the `LockRelease` Asset Ids
+    ///
+    /// EscrowBalances: map AssetId => u128
+    #[pallet::storage]
+    #[pallet::getter(fn escrow_balances)]
+    pub type EscrowBalances<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, u128, ValueQuery>;
+
+    /// The erc20 amounts minted by `deposit` minus the burned by `withdraw` of the `MintBurn`
+    /// Asset Ids, started from the `totalSupply()` of their erc20 contracts at the registration,
+    /// which the supply should never exceed
+    ///
+    /// NetBridged: map AssetId => u128
+    #[pallet::storage]
+    #[pallet::getter(fn net_bridged)]
+    pub type NetBridged<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, u128, ValueQuery>;
+
+    /// The gas limits of the erc20 contract calls of Asset Ids, `GAS_LIMIT` by default
+    ///
+    /// GasLimits: map AssetId => Option<u64>
+    #[pallet::storage]
+    #[pallet::getter(fn gas_limits)]
+    pub type GasLimits<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, u64, OptionQuery>;
+
+    /// The Decimals of Asset Ids and their Erc20 Contracts, if they differ
+    ///
+    /// Decimals: map AssetId => Option<(asset_decimals, erc20_decimals)>
+    #[pallet::storage]
+    #[pallet::getter(fn decimals)]
+    pub type Decimals<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, (u8, u8), OptionQuery>;
+
+    /// The Volume Limits of Asset Ids in each Direction
+    ///
+    /// VolumeLimits: double_map AssetId, Direction => Option<VolumeLimit>
+    #[pallet::storage]
+    #[pallet::getter(fn volume_limits)]
+    pub type VolumeLimits<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AssetId,
+        Twox64Concat,
+        Direction,
+        VolumeLimit<T::Balance, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// The total volume of Asset Ids in each Direction in the current window
+    ///
+    /// TotalVolumes: double_map AssetId, Direction => (window_start, volume)
+    #[pallet::storage]
+    #[pallet::getter(fn total_volumes)]
+    pub type TotalVolumes<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AssetId,
+        Twox64Concat,
+        Direction,
+        (T::BlockNumber, T::Balance),
+        ValueQuery,
+    >;
+
+    /// The volume of Substrate Accounts of Asset Ids in each Direction in the current window
+    ///
+    /// AccountVolumes: nmap (AssetId, Direction, AccountId) => (window_start, volume)
+    #[pallet::storage]
+    #[pallet::getter(fn account_volumes)]
+    pub type AccountVolumes<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Twox64Concat, T::AssetId>,
+            NMapKey<Twox64Concat, Direction>,
+            NMapKey<Twox64Concat, T::AccountId>,
+        ),
+        (T::BlockNumber, T::Balance),
+        ValueQuery,
+    >;
+
     /// The Assets can back foreign chain
     ///
-    /// AssetIds: Vec<AssetId>
+    /// BackForeign: map AssetId => Option<()>
     #[pallet::storage]
-    #[pallet::getter(fn back_foreign_assets)]
-    pub type BackForeign<T: Config> = StorageValue<_, Vec<T::AssetId>, ValueQuery>;
+    pub type BackForeign<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, (), OptionQuery>;
 
     /// The pallet admin key.
     #[pallet::storage]
     #[pallet::getter(fn admin_key)]
     pub(super) type Admin<T: Config> = StorageValue<_, T::AccountId>;
 
-    /// The Assets in emergency
+    /// The Assets in emergency and their paused directions
+    ///
+    /// Emergencies: map AssetId => PauseFlags
     #[pallet::storage]
     #[pallet::getter(fn emergencies)]
-    pub(super) type Emergencies<T: Config> = StorageValue<_, Vec<T::AssetId>, ValueQuery>;
+    pub(super) type Emergencies<T: Config> =
+        StorageMap<_, Twox64Concat, T::AssetId, PauseFlags, ValueQuery>;
+
+    /// The creation bytecode of the erc20 contract deployed by `register_and_deploy`
+    ///
+    /// Erc20Code: BoundedVec<u8, MaxErc20CodeSize>
+    #[pallet::storage]
+    #[pallet::getter(fn erc20_code)]
+    pub type Erc20Code<T: Config> =
+        StorageValue<_, BoundedVec<u8, T::MaxErc20CodeSize>, ValueQuery>;
+
+    /// The fee-free calls used by Substrate Accounts in the current period
+    ///
+    /// FreeCallsUsed: map AccountId => (period_start, used)
+    #[pallet::storage]
+    #[pallet::getter(fn free_calls_used)]
+    pub type FreeCallsUsed<T: Config> =
+        StorageMap<_, Twox64Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+    /// The nonces of the `withdraw_signed` signatures of Evm Addresses
+    ///
+    /// WithdrawNonces: map H160 => u64
+    #[pallet::storage]
+    #[pallet::getter(fn withdraw_nonces)]
+    pub type WithdrawNonces<T: Config> = StorageMap<_, Twox64Concat, H160, u64, ValueQuery>;
+
+    /// The locations of the reserve assets, e.g.
+    /// `../Parachain(1000)/PalletInstance(50)/GeneralIndex(1)`, transferred back to their
+    /// reserve chains by xcm instead of the relayer
+    ///
+    /// ReserveLocations: map AssetId => Option<MultiLocation>
+    #[pallet::storage]
+    #[pallet::getter(fn reserve_locations)]
+    pub type ReserveLocations<T: Config> =
+        StorageMap<_, Twox64Concat, T::AssetId, MultiLocation, OptionQuery>;
+
+    /// The destination formats of the back foreign transfers, `AccountId32` by default
+    ///
+    /// DestFormats: map AssetId => DestFormat
+    #[pallet::storage]
+    #[pallet::getter(fn dest_formats)]
+    pub type DestFormats<T: Config> =
+        StorageMap<_, Twox64Concat, T::AssetId, DestFormat, ValueQuery>;
+
+    /// The relayer paying the back foreign transfers on the foreign chains.
+    #[pallet::storage]
+    #[pallet::getter(fn relayer)]
+    pub type Relayer<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// The nonce of the next back foreign transfer
+    ///
+    /// BackForeignNonce: u64
+    #[pallet::storage]
+    #[pallet::getter(fn back_foreign_nonce)]
+    pub type BackForeignNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// The back foreign transfers by nonce, kept with the final status for `BackForeignRetention`
+    /// blocks after confirmed, refunded or reclaimed
+    ///
+    /// PendingBackForeign: map u64 => Option<BackForeignTransfer>
+    #[pallet::storage]
+    #[pallet::getter(fn pending_back_foreign)]
+    pub type PendingBackForeign<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        BackForeignTransfer<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// The nonces of the finished back foreign transfers by the block their retention ends
+    ///
+    /// BackForeignExpiries: map BlockNumber => BoundedVec<u64>
+    #[pallet::storage]
+    #[pallet::getter(fn back_foreign_expiries)]
+    pub type BackForeignExpiries<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        BoundedVec<u64, ConstU32<MAX_FINISHED_BACK_FOREIGN>>,
+        ValueQuery,
+    >;
+
+    /// The allowances of the native erc20 precompile, `u128::MAX` is unlimited, removed with the
+    /// owner account when it is reaped
+    ///
+    /// NativeAllowances: double_map AccountId, H160 => u128
+    #[pallet::storage]
+    #[pallet::getter(fn native_allowances)]
+    pub type NativeAllowances<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        H160,
+        u128,
+        ValueQuery,
+    >;
 
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -162,9 +655,9 @@ pub mod pallet {
         ClaimAccount(T::AccountId, H160),
         /// (account_id)
         Dissolve(T::AccountId),
-        /// (asset_id, account_id, evm_address, amount, erc20_contract)
+        /// (asset_id, from_account_id, to_evm_address, amount, erc20_contract)
         DepositExecuted(T::AssetId, T::AccountId, H160, T::Balance, H160),
-        /// (asset_id, account_id, evm_address, amount, erc20_contract)
+        /// (asset_id, to_account_id, from_evm_address, amount, erc20_contract)
         WithdrawExecuted(T::AssetId, T::AccountId, H160, T::Balance, H160),
         /// (account_id, amount, action)
         Teleport(T::AccountId, BalanceOf<T>, ActionType<T::AssetId>),
@@ -174,14 +667,44 @@ pub mod pallet {
         Register(T::AssetId, H160),
         /// (asset_id, erc20_contract)
         ForceUnRegister(T::AssetId, H160),
-        /// (asset_id)
-        Paused(T::AssetId),
-        // (asset_id)
-        UnPaused(T::AssetId),
-        PausedAll,
-        UnPausedAll,
+        /// (asset_id, direction), the direction is None for all directions
+        Paused(T::AssetId, Option<Direction>),
+        /// (asset_id, direction), the direction is None for all directions
+        UnPaused(T::AssetId, Option<Direction>),
+        /// (direction), the direction is None for all directions
+        PausedAll(Option<Direction>),
+        /// (direction), the direction is None for all directions
+        UnPausedAll(Option<Direction>),
         // (asset_id, remove)
         BackForeign(T::AssetId, bool),
+        /// (code_hash)
+        SetErc20Code(H256),
+        /// (asset_id, direction, limit)
+        SetVolumeLimit(T::AssetId, Direction, Option<VolumeLimit<T::Balance, T::BlockNumber>>),
+        /// (asset_id, direction, account_id), the account_id is None for the total volume
+        VolumeLimitReached(T::AssetId, Direction, Option<T::AccountId>),
+        /// (evm_address, nonce, error)
+        WithdrawSignedFailed(H160, u64, DispatchError),
+        /// (asset_id, gas_limit), the gas_limit is None for `GAS_LIMIT`
+        SetGasLimit(T::AssetId, Option<u64>),
+        /// (account_id)
+        SetRelayer(T::AccountId),
+        /// (nonce, account_id, asset_id, amount, dest)
+        BackForeignQueued(u64, T::AccountId, T::AssetId, T::Balance, BackForeignDest),
+        /// (nonce)
+        BackForeignConfirmed(u64),
+        /// (nonce)
+        BackForeignRefunded(u64),
+        /// (nonce)
+        BackForeignReclaimed(u64),
+        /// (asset_id, location), the location is None for the relayed asset
+        SetReserveLocation(T::AssetId, Option<MultiLocation>),
+        /// (account_id, asset_id, amount, reserve_chain, beneficiary)
+        BackForeignXcmSent(T::AccountId, T::AssetId, T::Balance, MultiLocation, MultiLocation),
+        /// (asset_id, format)
+        SetDestFormat(T::AssetId, DestFormat),
+        /// (asset_id, net_bridged, total_supply)
+        NetBridgedDrift(T::AssetId, u128, U256),
     }
 
     /// Error for evm accounts module.
@@ -207,16 +730,60 @@ pub mod pallet {
         ContractAddressHasMapped,
         /// Erc20 contract address has not mapped
         ContractAddressHasNotMapped,
-        /// Failed Erc20 contract call
+        /// Failed Erc20 contract call with an evm error
         ExecutedFailed,
         /// Require admin authority
         RequireAdmin,
-        /// Ban deposit and withdraw when in emergency
+        /// Ban deposit, withdraw or back foreign when in emergency
         InEmergency,
         /// Ban back to foreign
         BanBackForeign,
         /// Zero balance
         ZeroBalance,
+        /// The erc20 contract bytecode has not been set
+        Erc20CodeNotSet,
+        /// The erc20 contract creation bytecode is too large
+        Erc20CodeTooLarge,
+        /// The asset metadata has not been set
+        AssetMetadataNotSet,
+        /// The amount would leave dust after decimal scaling
+        DustAmount,
+        /// The volume exceeds the limit of the current window
+        VolumeLimitExceeded,
+        /// The escrow balance is insufficient to release
+        InsufficientEscrow,
+        /// The erc20 returned malformed data or `false`
+        InvalidReturnData,
+        /// The erc20 contract call reverted
+        EvmReverted,
+        /// The erc20 contract call ran out of gas
+        EvmOutOfGas,
+        /// The erc20 contract call failed with a fatal evm error
+        EvmFatal,
+        /// The gas limit is zero or exceeds `MAX_GAS_LIMIT`
+        InvalidGasLimit,
+        /// Require relayer authority
+        RequireRelayer,
+        /// The destination on the foreign chain is invalid
+        InvalidDest,
+        /// The back foreign transfer does not exist
+        BackForeignNotFound,
+        /// The back foreign transfer is not pending
+        BackForeignNotPending,
+        /// The back foreign transfer can't be reclaimed before its deadline
+        BackForeignNotExpired,
+        /// The xcm version of the location is not supported
+        BadXcmVersion,
+        /// Failed to send the xcm to the reserve chain
+        XcmSendFailed,
+        /// The net bridged amount equals the erc20 `totalSupply()`
+        NoDrift,
+        /// The erc20 balance is insufficient to withdraw
+        InsufficientErc20Balance,
+        /// Too many back foreign transfers are finished in this block, retry in the next one
+        TooManyFinishedBackForeign,
+        /// The decimals differ from the `decimals()` of the erc20 contract
+        Erc20DecimalsMismatch,
     }
 
     #[pallet::call]
@@ -226,11 +793,12 @@ pub mod pallet {
     {
         /// Claim account mapping between Substrate accounts and EVM accounts.
         /// Ensure eth_address has not been mapped.
+        /// The native balance of the unclaimed evm account is moved into the caller.
         /// Note: for general users
         ///
         /// - `eth_address`: The address to bind to the caller's account
         /// - `eth_signature`: A signature generated by the address to prove ownership
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::claim_account())]
         #[transactional]
         pub fn claim_account(
             origin: OriginFor<T>,
@@ -249,19 +817,12 @@ pub mod pallet {
 
             ensure!(eth_address == address, Error::<T>::InvalidSignature);
 
-            <T as pallet_assets::Config>::Currency::reserve(&who, T::ClaimBond::get())?;
-
-            SubAccounts::<T>::insert(eth_address, &who);
-            EvmAccounts::<T>::insert(&who, eth_address);
-
-            Self::deposit_event(Event::ClaimAccount(who, eth_address));
-
-            Ok(())
+            Self::do_claim_account(who, eth_address)
         }
 
         /// Dissolve substrate accounts and EVM accounts.
         /// Note: for general users
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::dissolve())]
         #[transactional]
         pub fn dissolve(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
@@ -274,6 +835,7 @@ pub mod pallet {
             );
 
             <T as pallet_assets::Config>::Currency::unreserve(&who, T::ClaimBond::get());
+            Self::keep_evm_nonce(&who, &T::UnclaimedAddressMapping::into_account_id(evm_account));
 
             SubAccounts::<T>::remove(evm_account);
             EvmAccounts::<T>::remove(&who);
@@ -288,7 +850,10 @@ pub mod pallet {
         ///
         /// - `asset_id`: The asset id
         /// - `amount`: Deposit amount
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::deposit()
+                .saturating_add(Pallet::<T>::evm_call_weight(*asset_id))
+        )]
         #[transactional]
         pub fn deposit(
             origin: OriginFor<T>,
@@ -296,25 +861,15 @@ pub mod pallet {
             amount: T::Balance,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+            ensure!(!Self::is_paused(asset_id, Direction::Deposit), Error::<T>::InEmergency);
             ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
 
             // 1. check evm account
             let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
 
-            // 2. burn asset
-            let _ = pallet_assets::Pallet::<T>::burn_from(asset_id, &who, amount)?;
-
-            // 3. mint erc20
-            let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
-
-            let inputs = mint_into_encode(evm_account, amount.unique_saturated_into());
+            let used_gas = Self::do_deposit(&who, asset_id, amount, evm_account)?;
 
-            Self::call_evm(erc20, inputs)?;
-
-            Self::deposit_event(Event::DepositExecuted(asset_id, who, evm_account, amount, erc20));
-
-            Ok(Pays::No.into())
+            Ok(Self::post_info(<T as Config>::WeightInfo::deposit(), used_gas, &who))
         }
 
         /// Withdraw from evm erc20 contracts into substrate assets
@@ -322,7 +877,10 @@ pub mod pallet {
         ///
         /// - `asset_id`: The asset id
         /// - `amount`: Withdraw amount
-        #[pallet::weight(1_000_000u64)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::withdraw()
+                .saturating_add(Pallet::<T>::evm_call_weight(*asset_id))
+        )]
         #[transactional]
         pub fn withdraw(
             origin: OriginFor<T>,
@@ -330,25 +888,15 @@ pub mod pallet {
             amount: T::Balance,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
+            ensure!(!Self::is_paused(asset_id, Direction::Withdraw), Error::<T>::InEmergency);
             ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
 
             // 1. check evm account
             let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
 
-            // 2. burn erc20
-            let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
-
-            let inputs = burn_from_encode(evm_account, amount.unique_saturated_into());
+            let used_gas = Self::do_withdraw(&who, evm_account, asset_id, amount, &who)?;
 
-            Self::call_evm(erc20, inputs)?;
-
-            // 3. mint asset
-            pallet_assets::Pallet::<T>::mint_into(asset_id, &who, amount)?;
-
-            Self::deposit_event(Event::WithdrawExecuted(asset_id, who, evm_account, amount, erc20));
-
-            Ok(Pays::No.into())
+            Ok(Self::post_info(<T as Config>::WeightInfo::withdraw(), used_gas, &who))
         }
 
         /// Teleport native currency between substrate account and evm address
@@ -358,9 +906,12 @@ pub mod pallet {
         /// - `amount`: Teleport amount
         /// - `action`: (1) Direct(H160): direct transfer into unchecked evm address (2)
         ///   FromSubToEth: transfer from substrate account to mapped evm address (3) FromEthToSub:
-        ///   transfer from mapped evm address to substrate account
-        /// - companion with `relay`: (4) BackForeign(asset_id): transfer assets back foreign chain
-        #[pallet::weight(1_000_000u64)]
+        ///   transfer from mapped evm address to substrate account, (2) and (3) are no-ops if the
+        ///   `AddressMapping` is `ClaimedAddressMapping`
+        /// - companion with `relay`: (4) BackForeign(asset_id): transfer assets back to the caller
+        ///   on the foreign chain, the same as `transfer_back_foreign` with the caller as the
+        ///   destination, only for the assets of the `AccountId32` destinations
+        #[pallet::weight(<T as Config>::WeightInfo::teleport())]
         #[transactional]
         pub fn teleport(
             origin: OriginFor<T>,
@@ -388,16 +939,22 @@ pub mod pallet {
                     false,
                 ),
                 ActionType::BackForeign(asset_id) => {
-                    // ensure asset_id registered in back_foreign list
-                    ensure!(Self::is_in_back_foreign(asset_id), Error::<T>::BanBackForeign);
-                    ensure!(!Self::is_in_emergency(asset_id), Error::<T>::InEmergency);
-
+                    // the caller is the destination, which is checked by `DestFormats` as well
+                    ensure!(
+                        Self::dest_formats(asset_id) == DestFormat::AccountId32,
+                        Error::<T>::InvalidDest
+                    );
                     let amount: u128 = amount.unique_saturated_into();
-                    // burn asset first, then relay will transfer back `who`.
-                    let _ = pallet_assets::Pallet::<T>::burn_from(
-                        asset_id,
+                    let dest = BackForeignDest::try_from(who.encode())
+                        .map_err(|_| Error::<T>::InvalidDest)?;
+                    let beneficiary = T::AccountIdToMultiLocation::convert(who.clone());
+
+                    Self::do_back_foreign(
                         &who,
+                        asset_id,
                         amount.unique_saturated_into(),
+                        dest,
+                        Some(beneficiary),
                     )?;
 
                     (who.clone(), who.clone(), true)
@@ -413,29 +970,31 @@ pub mod pallet {
                 )?;
             }
 
-            Self::deposit_event(Event::Teleport(who, amount, action));
+            Self::deposit_event(Event::Teleport(who.clone(), amount, action));
 
-            Ok(Pays::No.into())
+            Ok(Self::pays_fee(&who).into())
         }
 
         /// Register substrate assets and erc20 contracts
-        /// Note: for admin
+        /// Note: for `RegisterOrigin`
         ///
         /// - `asset_id`: The asset id
         /// - `erc20`: The erc20 contract address
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::register().saturating_add(Pallet::<T>::gas_weight(GAS_LIMIT))
+        )]
         pub fn register(
             origin: OriginFor<T>,
             asset_id: T::AssetId,
             erc20: H160,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
+            T::RegisterOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
 
             // ensure asset_id and erc20 address has not been mapped
             ensure!(!Erc20s::<T>::contains_key(asset_id), Error::<T>::AssetIdHasMapped);
             ensure!(!AssetIds::<T>::contains_key(erc20), Error::<T>::ContractAddressHasMapped);
 
+            Self::init_net_bridged(asset_id, erc20)?;
             Erc20s::<T>::insert(asset_id, erc20);
             AssetIds::<T>::insert(erc20, asset_id);
 
@@ -444,114 +1003,107 @@ pub mod pallet {
             Ok(Pays::No.into())
         }
 
-        /// Pause assets bridge deposit and withdraw
-        /// Note: for admin
+        /// Pause assets bridge deposit, withdraw and back foreign
+        /// Note: for `PauseOrigin`
         ///
         /// - `asset_id`: None will pause all, Some(id) will pause the specified asset
-        #[pallet::weight(100_000_000u64)]
+        /// - `direction`: None will pause all directions, Some(direction) will pause the specified
+        ///   direction
+        #[pallet::weight(
+            <T as Config>::WeightInfo::pause(Pallet::<T>::assets_to_pause(*asset_id))
+        )]
         pub fn pause(
             origin: OriginFor<T>,
             asset_id: Option<T::AssetId>,
+            direction: Option<Direction>,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
-
-            Emergencies::<T>::try_mutate(|emergencies| {
-                if let Some(id) = asset_id {
-                    // ensure asset_id and erc20 address has not been mapped
-                    ensure!(Erc20s::<T>::contains_key(id), Error::<T>::AssetIdHasNotMapped);
-                    if !Self::is_in_emergency(id) {
-                        emergencies.push(id);
-
-                        Self::deposit_event(Event::Paused(id));
-                    }
-                } else {
-                    emergencies.truncate(0);
-                    for id in AssetIds::<T>::iter_values() {
-                        emergencies.push(id);
-                    }
-
-                    Self::deposit_event(Event::PausedAll);
+            T::PauseOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            if let Some(id) = asset_id {
+                // ensure asset_id and erc20 address has been mapped
+                ensure!(Erc20s::<T>::contains_key(id), Error::<T>::AssetIdHasNotMapped);
+
+                if Self::set_paused(id, direction, true) {
+                    Self::deposit_event(Event::Paused(id, direction));
+                }
+            } else {
+                for id in Erc20s::<T>::iter_keys() {
+                    Self::set_paused(id, direction, true);
                 }
 
-                Ok(Pays::No.into())
-            })
+                Self::deposit_event(Event::PausedAll(direction));
+            }
+
+            Ok(Pays::No.into())
         }
 
-        /// Unpause assets bridge deposit and withdraw
-        /// Note: for admin
+        /// Unpause assets bridge deposit, withdraw and back foreign
+        /// Note: for `PauseOrigin`
         ///
         /// - `asset_id`: None will unpause all, Some(id) will unpause the specified asset
-        #[pallet::weight(100_000_000u64)]
+        /// - `direction`: None will unpause all directions, Some(direction) will unpause the
+        ///   specified direction
+        #[pallet::weight(
+            <T as Config>::WeightInfo::unpause(Pallet::<T>::assets_to_pause(*asset_id))
+        )]
         pub fn unpause(
             origin: OriginFor<T>,
             asset_id: Option<T::AssetId>,
+            direction: Option<Direction>,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
-
-            Emergencies::<T>::try_mutate(|emergencies| {
-                if let Some(id) = asset_id {
-                    // ensure asset_id and erc20 address has been mapped
-                    ensure!(Erc20s::<T>::contains_key(id), Error::<T>::AssetIdHasNotMapped);
+            T::PauseOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
 
-                    if Self::is_in_emergency(id) {
-                        emergencies.retain(|&emergency| emergency != id);
+            if let Some(id) = asset_id {
+                // ensure asset_id and erc20 address has been mapped
+                ensure!(Erc20s::<T>::contains_key(id), Error::<T>::AssetIdHasNotMapped);
 
-                        Self::deposit_event(Event::UnPaused(id));
-                    }
-                } else {
-                    emergencies.truncate(0);
-
-                    Self::deposit_event(Event::UnPausedAll);
+                if Self::set_paused(id, direction, false) {
+                    Self::deposit_event(Event::UnPaused(id, direction));
+                }
+            } else {
+                for id in Self::paused_assets() {
+                    Self::set_paused(id, direction, false);
                 }
 
-                Ok(Pays::No.into())
-            })
+                Self::deposit_event(Event::UnPausedAll(direction));
+            }
+
+            Ok(Pays::No.into())
         }
 
         /// Add assets which can back add_back_foreign chain
-        /// Note: for admin
+        /// Note: for `BackForeignOrigin`
         ///
         /// - `asset_id`:
-        #[pallet::weight(100_000_000u64)]
+        #[pallet::weight(<T as Config>::WeightInfo::back_foreign())]
         pub fn back_foreign(
             origin: OriginFor<T>,
             asset_id: T::AssetId,
             remove: bool,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-            ensure!(Some(who) == Self::admin_key(), Error::<T>::RequireAdmin);
-
-            BackForeign::<T>::try_mutate(|foreigns| {
-                if remove {
-                    foreigns.retain(|id| *id != asset_id);
-                } else if !Self::is_in_back_foreign(asset_id) {
-                    foreigns.push(asset_id);
-                } else {
-                    return Ok(Pays::No.into());
-                }
+            T::BackForeignOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            if remove {
+                BackForeign::<T>::remove(asset_id);
+            } else if !Self::is_in_back_foreign(asset_id) {
+                BackForeign::<T>::insert(asset_id, ());
+            } else {
+                return Ok(Pays::No.into());
+            }
 
-                Self::deposit_event(Event::BackForeign(asset_id, remove));
+            Self::deposit_event(Event::BackForeign(asset_id, remove));
 
-                Ok(Pays::No.into())
-            })
+            Ok(Pays::No.into())
         }
 
         /// Set this pallet admin key
-        /// Note: for super admin
-        #[pallet::weight(100_000_000u64)]
+        /// Note: for `AdminOrigin`
+        #[pallet::weight(<T as Config>::WeightInfo::set_admin())]
         pub fn set_admin(
             origin: OriginFor<T>,
             new_admin: <T::Lookup as StaticLookup>::Source,
         ) -> DispatchResultWithPostInfo {
-            let require = match ensure_signed_or_root(origin) {
-                Ok(s) if s == Self::admin_key() => true,
-                Ok(None) => true,
-                _ => false,
-            };
-
-            ensure!(require, Error::<T>::RequireAdmin);
+            T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
 
             let new_admin = T::Lookup::lookup(new_admin)?;
 
@@ -563,13 +1115,13 @@ pub mod pallet {
         }
 
         /// Force unregister substrate assets and erc20 contracts
-        /// Note: for super admin
-        #[pallet::weight(100_000_000u64)]
+        /// Note: for `AdminOrigin`
+        #[pallet::weight(<T as Config>::WeightInfo::force_unregister())]
         pub fn force_unregister(
             origin: OriginFor<T>,
             asset_id: T::AssetId,
         ) -> DispatchResultWithPostInfo {
-            ensure_root(origin)?;
+            T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
 
             let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::AssetIdHasNotMapped)?;
 
@@ -577,53 +1129,1550 @@ pub mod pallet {
 
             Erc20s::<T>::remove(asset_id);
             AssetIds::<T>::remove(erc20);
+            Decimals::<T>::remove(asset_id);
+            // keep the escrow balance for auditing, the net bridged amount starts again from the
+            // erc20 supply when the asset is registered again
+            NetBridged::<T>::remove(asset_id);
+            BridgeModes::<T>::remove(asset_id);
+            GasLimits::<T>::remove(asset_id);
 
             // clear emergency
-            if Self::is_in_emergency(asset_id) {
-                Emergencies::<T>::mutate(|emergencies| {
-                    emergencies.retain(|&emergency| emergency != asset_id);
-                })
-            }
+            Emergencies::<T>::remove(asset_id);
 
             Self::deposit_event(Event::ForceUnRegister(asset_id, erc20));
 
             Ok(Pays::No.into())
         }
-    }
-}
 
-impl<T: Config> Pallet<T>
-where
-    DispatchError: From<<<T as pallet_evm::Config>::Runner as pallet_evm::Runner<T>>::Error>,
-{
-    fn call_evm(erc20: H160, inputs: Vec<u8>) -> DispatchResult {
-        let info = T::Runner::call(
-            T::EvmCaller::get(),
-            erc20,
-            inputs,
-            U256::default(),
-            3_000_000,
-            None,
-            None,
-            None,
-            Vec::new(),
-            false,
-            true,
-            T::config(),
-        )
-        .map_err(|e| e.error.into())?;
+        /// Deposit substrate assets into evm erc20 contracts of any evm address
+        /// Note: for general users
+        ///
+        /// - `asset_id`: The asset id
+        /// - `amount`: Deposit amount
+        /// - `recipient`: The evm address to receive the erc20 tokens, need not be mapped
+        #[pallet::weight(
+            <T as Config>::WeightInfo::deposit_to()
+                .saturating_add(Pallet::<T>::evm_call_weight(*asset_id))
+        )]
+        #[transactional]
+        pub fn deposit_to(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            amount: T::Balance,
+            recipient: H160,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::is_paused(asset_id, Direction::Deposit), Error::<T>::InEmergency);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
 
-        match info.exit_reason {
-            ExitReason::Succeed(_) => Ok(()),
-            _ => Err(Error::<T>::ExecutedFailed.into()),
+            let used_gas = Self::do_deposit(&who, asset_id, amount, recipient)?;
+
+            Ok(Self::post_info(<T as Config>::WeightInfo::deposit_to(), used_gas, &who))
         }
-    }
 
-    fn is_in_emergency(asset_id: T::AssetId) -> bool {
-        Self::emergencies().iter().any(|&emergency| emergency == asset_id)
-    }
+        /// Withdraw from evm erc20 contracts into substrate assets of any substrate account
+        /// Ensure the caller's eth_address has been mapped
+        /// Note: for general users
+        ///
+        /// - `asset_id`: The asset id
+        /// - `amount`: Withdraw amount
+        /// - `dest`: The substrate account to receive the assets
+        #[pallet::weight(
+            <T as Config>::WeightInfo::withdraw_to()
+                .saturating_add(Pallet::<T>::evm_call_weight(*asset_id))
+        )]
+        #[transactional]
+        pub fn withdraw_to(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            amount: T::Balance,
+            dest: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!Self::is_paused(asset_id, Direction::Withdraw), Error::<T>::InEmergency);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
+
+            let dest = T::Lookup::lookup(dest)?;
+
+            // 1. check evm account
+            let evm_account = Self::evm_accounts(&who).ok_or(Error::<T>::EthAddressHasNotMapped)?;
+
+            let used_gas = Self::do_withdraw(&who, evm_account, asset_id, amount, &dest)?;
+
+            Ok(Self::post_info(<T as Config>::WeightInfo::withdraw_to(), used_gas, &who))
+        }
+
+        /// Deploy an erc20 contract for substrate assets and register them
+        /// The name, symbol and decimals of the erc20 contract come from the asset metadata
+        /// Note: for `RegisterOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        #[pallet::weight(
+            <T as Config>::WeightInfo::register_and_deploy()
+                .saturating_add(Pallet::<T>::evm_create_weight())
+                .saturating_add(Pallet::<T>::gas_weight(GAS_LIMIT))
+        )]
+        #[transactional]
+        pub fn register_and_deploy(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+        ) -> DispatchResultWithPostInfo {
+            T::RegisterOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            ensure!(!Erc20s::<T>::contains_key(asset_id), Error::<T>::AssetIdHasMapped);
+
+            let code = Self::erc20_code();
+            ensure!(!code.is_empty(), Error::<T>::Erc20CodeNotSet);
+
+            let name = pallet_assets::Pallet::<T>::name(asset_id);
+            let symbol = pallet_assets::Pallet::<T>::symbol(asset_id);
+            let decimals = pallet_assets::Pallet::<T>::decimals(asset_id);
+            ensure!(!name.is_empty() && !symbol.is_empty(), Error::<T>::AssetMetadataNotSet);
+
+            let mut init = code.into_inner();
+            init.extend(erc20_constructor_encode(&name, &symbol, decimals));
+
+            let erc20 = Self::create_evm(init)?;
+
+            ensure!(!AssetIds::<T>::contains_key(erc20), Error::<T>::ContractAddressHasMapped);
+
+            Self::init_net_bridged(asset_id, erc20)?;
+            Erc20s::<T>::insert(asset_id, erc20);
+            AssetIds::<T>::insert(erc20, asset_id);
+
+            Self::deposit_event(Event::Register(asset_id, erc20));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Set the erc20 contract creation bytecode used by `register_and_deploy`
+        /// Note: for `AdminOrigin`
+        ///
+        /// - `code`: The creation bytecode without constructor arguments
+        #[pallet::weight(<T as Config>::WeightInfo::set_erc20_code(code.len() as u32))]
+        pub fn set_erc20_code(origin: OriginFor<T>, code: Vec<u8>) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            let code_hash = H256::from(keccak_256(&code));
+            let code: BoundedVec<u8, T::MaxErc20CodeSize> =
+                code.try_into().map_err(|_| Error::<T>::Erc20CodeTooLarge)?;
+
+            Erc20Code::<T>::put(code);
+
+            Self::deposit_event(Event::SetErc20Code(code_hash));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Register substrate assets and erc20 contracts with different decimals
+        /// The amounts will be scaled between the asset decimals and the erc20 decimals
+        /// Note: for `RegisterOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        /// - `erc20`: The erc20 contract address
+        /// - `erc20_decimals`: The decimals of the erc20 contract, checked against its `decimals()`
+        #[pallet::weight(
+            <T as Config>::WeightInfo::register_with_decimals()
+                .saturating_add(Pallet::<T>::gas_weight(GAS_LIMIT).saturating_mul(2))
+        )]
+        pub fn register_with_decimals(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            erc20: H160,
+            erc20_decimals: u8,
+        ) -> DispatchResultWithPostInfo {
+            T::RegisterOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            Self::do_register_with_decimals(asset_id, erc20, erc20_decimals)?;
+            Self::init_net_bridged(asset_id, erc20)?;
+
+            Self::deposit_event(Event::Register(asset_id, erc20));
+
+            Ok(Pays::No.into())
+        }
 
-    fn is_in_back_foreign(asset_id: T::AssetId) -> bool {
-        Self::back_foreign_assets().iter().any(|&id| id == asset_id)
+        /// Claim account mapping between Substrate accounts and EVM accounts
+        /// by an EIP-712 typed data signature.
+        /// Ensure eth_address has not been mapped.
+        /// The native balance of the unclaimed evm account is moved into the caller.
+        /// Note: for general users
+        ///
+        /// - `eth_address`: The address to bind to the caller's account
+        /// - `eth_signature`: A `Claim(bytes substrateAccount)` typed data signature generated by
+        ///   the address to prove ownership
+        #[pallet::weight(<T as Config>::WeightInfo::claim_account_eip712())]
+        #[transactional]
+        pub fn claim_account_eip712(
+            origin: OriginFor<T>,
+            eth_address: H160,
+            eth_signature: EcdsaSignature,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            // ensure account_id and eth_address has not been mapped
+            ensure!(!EvmAccounts::<T>::contains_key(&who), Error::<T>::AccountIdHasMapped);
+            ensure!(!SubAccounts::<T>::contains_key(eth_address), Error::<T>::EthAddressHasMapped);
+
+            // recover evm address from typed data signature
+            let address = eth_recover_typed(
+                &eth_signature,
+                &Self::eip712_domain_separator(),
+                &who.using_encoded(eip712_claim_hash),
+            )
+            .ok_or(Error::<T>::BadSignature)?;
+
+            ensure!(eth_address == address, Error::<T>::InvalidSignature);
+
+            Self::do_claim_account(who, eth_address)
+        }
+
+        /// Set the volume limit of assets bridge transfers
+        /// Note: for `RegisterOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        /// - `direction`: The direction of the transfers
+        /// - `limit`: None will remove the limit
+        #[pallet::weight(<T as Config>::WeightInfo::set_volume_limit())]
+        pub fn set_volume_limit(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            direction: Direction,
+            limit: Option<VolumeLimit<T::Balance, T::BlockNumber>>,
+        ) -> DispatchResultWithPostInfo {
+            T::RegisterOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            VolumeLimits::<T>::set(asset_id, direction, limit.clone());
+
+            Self::deposit_event(Event::SetVolumeLimit(asset_id, direction, limit));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Withdraw from evm erc20 contracts into substrate assets by an unsigned transaction,
+        /// authorized by the signature of the evm address.
+        /// Ensure eth_address has been mapped
+        /// Note: for general users without native currency
+        ///
+        /// - `eth_address`: The evm address to withdraw from
+        /// - `asset_id`: The asset id
+        /// - `amount`: Withdraw amount
+        /// - `eth_signature`: A signature of `withdraw_message` with the current nonce generated by
+        ///   the address
+        ///
+        /// The nonce is consumed even if the withdraw fails, which emits `WithdrawSignedFailed`.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::withdraw_signed()
+                .saturating_add(Pallet::<T>::evm_call_weight(*asset_id))
+        )]
+        pub fn withdraw_signed(
+            origin: OriginFor<T>,
+            eth_address: H160,
+            asset_id: T::AssetId,
+            amount: T::Balance,
+            eth_signature: EcdsaSignature,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+
+            let nonce = Self::check_withdraw_signed(eth_address, asset_id, amount, &eth_signature)?;
+
+            // consume the nonce, or the fee-free transaction could be replayed until it succeeds
+            WithdrawNonces::<T>::insert(eth_address, nonce.saturating_add(1));
+
+            match Self::withdraw_from_evm(eth_address, asset_id, amount) {
+                Ok(used_gas) => Ok(Some(
+                    <T as Config>::WeightInfo::withdraw_signed()
+                        .saturating_add(Self::used_gas_weight(used_gas)),
+                )
+                .into()),
+                Err(e) => {
+                    Self::deposit_event(Event::WithdrawSignedFailed(eth_address, nonce, e));
+                    Ok(().into())
+                },
+            }
+        }
+
+        /// Register substrate assets and third-party erc20 contracts in `LockRelease` mode
+        /// `withdraw` locks the erc20 tokens approved to the escrow `EvmCaller`, and `deposit`
+        /// releases them from the escrow
+        /// Note: for `RegisterOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        /// - `erc20`: The erc20 contract address
+        /// - `erc20_decimals`: The decimals of the erc20 contract, checked against its `decimals()`
+        #[pallet::weight(
+            <T as Config>::WeightInfo::register_lock_release()
+                .saturating_add(Pallet::<T>::gas_weight(GAS_LIMIT))
+        )]
+        pub fn register_lock_release(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            erc20: H160,
+            erc20_decimals: u8,
+        ) -> DispatchResultWithPostInfo {
+            T::RegisterOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            Self::do_register_with_decimals(asset_id, erc20, erc20_decimals)?;
+            BridgeModes::<T>::insert(asset_id, BridgeMode::LockRelease);
+
+            Self::deposit_event(Event::Register(asset_id, erc20));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Set the gas limit of the erc20 contract calls of assets
+        /// Note: for `RegisterOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        /// - `gas_limit`: None will reset to `GAS_LIMIT`
+        #[pallet::weight(<T as Config>::WeightInfo::set_gas_limit())]
+        pub fn set_gas_limit(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            gas_limit: Option<u64>,
+        ) -> DispatchResultWithPostInfo {
+            T::RegisterOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+            ensure!(Erc20s::<T>::contains_key(asset_id), Error::<T>::AssetIdHasNotMapped);
+            if let Some(gas_limit) = gas_limit {
+                ensure!(gas_limit > 0 && gas_limit <= MAX_GAS_LIMIT, Error::<T>::InvalidGasLimit);
+            }
+
+            GasLimits::<T>::set(asset_id, gas_limit);
+
+            Self::deposit_event(Event::SetGasLimit(asset_id, gas_limit));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Set the relayer of the back foreign transfers
+        /// Note: for `BackForeignOrigin`
+        #[pallet::weight(<T as Config>::WeightInfo::set_relayer())]
+        pub fn set_relayer(
+            origin: OriginFor<T>,
+            relayer: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResultWithPostInfo {
+            T::BackForeignOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            let relayer = T::Lookup::lookup(relayer)?;
+
+            Relayer::<T>::put(relayer.clone());
+
+            Self::deposit_event(Event::SetRelayer(relayer));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Confirm the back foreign transfer has been paid on the foreign chain
+        /// Note: for relayer
+        ///
+        /// - `nonce`: The nonce of the back foreign transfer
+        #[pallet::weight(<T as Config>::WeightInfo::confirm_back_foreign())]
+        pub fn confirm_back_foreign(
+            origin: OriginFor<T>,
+            nonce: u64,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Some(who) == Self::relayer(), Error::<T>::RequireRelayer);
+
+            Self::finish_back_foreign(nonce, BackForeignStatus::Confirmed)?;
+
+            Self::deposit_event(Event::BackForeignConfirmed(nonce));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Refund the back foreign transfer failed on the foreign chain, the burned asset is
+        /// minted back to its owner
+        /// Note: for relayer
+        ///
+        /// - `nonce`: The nonce of the back foreign transfer
+        #[pallet::weight(<T as Config>::WeightInfo::refund_back_foreign())]
+        #[transactional]
+        pub fn refund_back_foreign(origin: OriginFor<T>, nonce: u64) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Some(who) == Self::relayer(), Error::<T>::RequireRelayer);
+
+            let transfer = Self::finish_back_foreign(nonce, BackForeignStatus::Refunded)?;
+            Self::cancel_back_foreign(&transfer)?;
+
+            Self::deposit_event(Event::BackForeignRefunded(nonce));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Reclaim the back foreign transfer not confirmed or refunded by the relayer before its
+        /// deadline, the burned asset is minted back to the caller
+        /// Note: for the owner of the back foreign transfer
+        ///
+        /// - `nonce`: The nonce of the back foreign transfer
+        #[pallet::weight(<T as Config>::WeightInfo::reclaim_back_foreign())]
+        #[transactional]
+        pub fn reclaim_back_foreign(
+            origin: OriginFor<T>,
+            nonce: u64,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let transfer =
+                Self::pending_back_foreign(nonce).ok_or(Error::<T>::BackForeignNotFound)?;
+            ensure!(transfer.who == who, Error::<T>::BackForeignNotFound);
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= transfer.deadline,
+                Error::<T>::BackForeignNotExpired
+            );
+
+            let transfer = Self::finish_back_foreign(nonce, BackForeignStatus::Reclaimed)?;
+            Self::cancel_back_foreign(&transfer)?;
+
+            Self::deposit_event(Event::BackForeignReclaimed(nonce));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Set the reserve location of assets, the back foreign transfers of them are sent to the
+        /// reserve chain by xcm
+        /// Note: for `BackForeignOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        /// - `location`: The asset location, None will transfer back by the relayer
+        #[pallet::weight(<T as Config>::WeightInfo::set_reserve_location())]
+        pub fn set_reserve_location(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            location: Option<Box<VersionedMultiLocation>>,
+        ) -> DispatchResultWithPostInfo {
+            T::BackForeignOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            let location = location
+                .map(|location| MultiLocation::try_from(*location))
+                .transpose()
+                .map_err(|_| Error::<T>::BadXcmVersion)?;
+
+            ReserveLocations::<T>::set(asset_id, location.clone());
+
+            Self::deposit_event(Event::SetReserveLocation(asset_id, location));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Set the destination format of the back foreign transfers of assets
+        /// Note: for `BackForeignOrigin`
+        ///
+        /// - `asset_id`: The asset id
+        /// - `format`: The format checked by `transfer_back_foreign`
+        #[pallet::weight(<T as Config>::WeightInfo::set_dest_format())]
+        pub fn set_dest_format(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            format: DestFormat,
+        ) -> DispatchResultWithPostInfo {
+            T::BackForeignOrigin::ensure_origin(origin).map_err(|_| Error::<T>::RequireAdmin)?;
+
+            DestFormats::<T>::insert(asset_id, format);
+
+            Self::deposit_event(Event::SetDestFormat(asset_id, format));
+
+            Ok(Pays::No.into())
+        }
+
+        /// Transfer assets back to the destination on the foreign chain
+        /// Note: for general users
+        ///
+        /// - `asset_id`: The asset id in the back foreign list
+        /// - `amount`: The amount of `asset_id`
+        /// - `dest`: The destination on the foreign chain, checked by the `DestFormats` of
+        ///   `asset_id`
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_back_foreign())]
+        #[transactional]
+        pub fn transfer_back_foreign(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            amount: T::Balance,
+            dest: BackForeignDest,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
+
+            let beneficiary = Self::check_dest(asset_id, &dest)?;
+
+            Self::do_back_foreign(&who, asset_id, amount, dest, beneficiary)?;
+
+            Ok(Self::pays_fee(&who).into())
+        }
+
+        /// Pause all the directions of an asset whose erc20 `totalSupply()` drifts above its
+        /// `NetBridged`
+        /// Note: an unsigned transaction submitted by the off-chain worker
+        ///
+        /// - `asset_id`: The `MintBurn` asset id
+        #[pallet::weight(
+            <T as Config>::WeightInfo::report_drift()
+                .saturating_add(Pallet::<T>::gas_weight(GAS_LIMIT))
+        )]
+        pub fn report_drift(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+
+            let (net_bridged, total_supply) =
+                Self::net_bridged_drift(asset_id)?.ok_or(Error::<T>::NoDrift)?;
+
+            Self::deposit_event(Event::NetBridgedDrift(asset_id, net_bridged, total_supply));
+
+            if Self::set_paused(asset_id, None, true) {
+                Self::deposit_event(Event::Paused(asset_id, None));
+            }
+
+            Ok(Pays::No.into())
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+    where
+        DispatchError: From<<<T as pallet_evm::Config>::Runner as pallet_evm::Runner<T>>::Error>,
+    {
+        /// Remove the finished back foreign transfers whose retention ends in this block.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let expired = BackForeignExpiries::<T>::take(now);
+            let count = expired.len() as u64;
+            for nonce in expired {
+                PendingBackForeign::<T>::remove(nonce);
+            }
+
+            T::DbWeight::get().reads_writes(1, count.saturating_add(1))
+        }
+
+        fn offchain_worker(now: T::BlockNumber) {
+            let interval = T::DriftCheckInterval::get();
+            if interval.is_zero() || !(now % interval).is_zero() {
+                return;
+            }
+
+            for asset_id in Erc20s::<T>::iter_keys() {
+                if Self::emergencies(asset_id) == PauseFlags::all() {
+                    continue;
+                }
+
+                match Self::net_bridged_drift(asset_id) {
+                    Ok(None) => {},
+                    Ok(Some((net_bridged, total_supply))) => {
+                        log::warn!(
+                            target: "runtime::assets-bridge",
+                            "asset {:?} drifts, net bridged: {}, erc20 total supply: {}",
+                            asset_id,
+                            net_bridged,
+                            total_supply
+                        );
+
+                        let call = Call::report_drift { asset_id };
+                        if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+                            .is_err()
+                        {
+                            log::error!(
+                                target: "runtime::assets-bridge",
+                                "failed to submit the drift report of asset {:?}",
+                                asset_id
+                            );
+                        }
+                    },
+                    Err(e) => log::warn!(
+                        target: "runtime::assets-bridge",
+                        "failed to check the net bridged of asset {:?}: {:?}",
+                        asset_id,
+                        e
+                    ),
+                }
+            }
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: T::BlockNumber) -> Result<(), &'static str> {
+            for asset_id in Erc20s::<T>::iter_keys() {
+                let drift = Self::net_bridged_drift(asset_id)
+                    .map_err(|_| "the erc20 totalSupply() should be readable")?;
+                if let Some((net_bridged, total_supply)) = drift {
+                    log::error!(
+                        target: "runtime::assets-bridge",
+                        "asset {:?} drifts, net bridged: {}, erc20 total supply: {}",
+                        asset_id,
+                        net_bridged,
+                        total_supply
+                    );
+                    return Err("the erc20 totalSupply() should not exceed the net bridged amount");
+                }
+            }
+            for nonce in BackForeignExpiries::<T>::iter_values().flatten() {
+                ensure!(
+                    Self::pending_back_foreign(nonce)
+                        .map_or(false, |transfer| transfer.status != BackForeignStatus::Pending),
+                    "the retained back foreign transfers should be finished"
+                );
+            }
+            for owner in NativeAllowances::<T>::iter_keys().map(|(owner, _)| owner) {
+                ensure!(
+                    Self::can_hold_native_allowances(&owner),
+                    "the owners of the native allowances should be provided"
+                );
+            }
+
+            Ok(())
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T>
+    where
+        DispatchError: From<<<T as pallet_evm::Config>::Runner as pallet_evm::Runner<T>>::Error>,
+    {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            match call {
+                Call::withdraw_signed { eth_address, asset_id, amount, eth_signature } => {
+                    let nonce = Self::check_withdraw_signed(
+                        *eth_address,
+                        *asset_id,
+                        *amount,
+                        eth_signature,
+                    )
+                    .map_err(|e| {
+                        if e == Error::<T>::BadSignature.into() ||
+                            e == Error::<T>::InvalidSignature.into()
+                        {
+                            InvalidTransaction::BadProof
+                        } else {
+                            InvalidTransaction::Call
+                        }
+                    })?;
+                    // the nonce is consumed even if the withdraw fails, reject the fee-free
+                    // transaction which can't succeed
+                    Self::check_withdraw_signed_funds(*eth_address, *asset_id, *amount)
+                        .map_err(|_| InvalidTransaction::Call)?;
+
+                    ValidTransaction::with_tag_prefix("AssetsBridgeWithdrawSigned")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides((eth_address, nonce))
+                        .propagate(true)
+                        .build()
+                },
+                Call::report_drift { asset_id } => {
+                    ensure!(
+                        Self::emergencies(asset_id) != PauseFlags::all(),
+                        InvalidTransaction::Stale
+                    );
+                    ensure!(
+                        matches!(Self::net_bridged_drift(*asset_id), Ok(Some(_))),
+                        InvalidTransaction::Call
+                    );
+
+                    ValidTransaction::with_tag_prefix("AssetsBridgeReportDrift")
+                        .priority(T::UnsignedPriority::get())
+                        .and_provides(asset_id)
+                        .longevity(T::DriftCheckInterval::get().unique_saturated_into())
+                        .propagate(true)
+                        .build()
+                },
+                _ => InvalidTransaction::Call.into(),
+            }
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// The gas limit of the erc20 contract calls of `asset_id`.
+    pub fn gas_limit(asset_id: T::AssetId) -> u64 {
+        Self::gas_limits(asset_id).unwrap_or(GAS_LIMIT)
+    }
+
+    /// The weight of the inner erc20 contract call of `asset_id`, up to its gas limit, with the
+    /// `GasLimits` read when the call weight is computed.
+    pub fn evm_call_weight(asset_id: T::AssetId) -> Weight {
+        Self::gas_weight(Self::gas_limit(asset_id)).saturating_add(T::DbWeight::get().reads(1))
+    }
+
+    /// The weight of the `gas` used by the inner erc20 contract calls.
+    pub fn gas_weight(gas: u64) -> Weight {
+        <T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(gas, true)
+    }
+
+    /// The weight of the inner erc20 contract creation, up to its gas limit.
+    pub fn evm_create_weight() -> Weight {
+        <T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(CREATE_GAS_LIMIT, true)
+    }
+
+    /// All the registered assets with their erc20 contracts.
+    pub fn registrations() -> Vec<AssetRegistration<T::AssetId>> {
+        Erc20s::<T>::iter()
+            .map(|(asset_id, erc20)| AssetRegistration {
+                asset_id,
+                erc20,
+                paused: Self::emergencies(asset_id),
+                back_foreign: Self::is_in_back_foreign(asset_id),
+            })
+            .collect()
+    }
+
+    /// The EIP-712 domain separator of the typed data signatures,
+    /// which binds the pallet name, the evm chain id and the genesis hash.
+    pub fn eip712_domain_separator() -> [u8; 32] {
+        let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+
+        eip712_domain_hash(
+            <Self as PalletInfoAccess>::name().as_bytes(),
+            <T as pallet_evm::Config>::ChainId::get(),
+            genesis_hash.as_ref(),
+        )
+    }
+
+    /// Scale the asset `amount` into the erc20 amount by their decimals.
+    /// Reject the amount which would leave dust.
+    pub fn erc20_amount(asset_id: T::AssetId, amount: T::Balance) -> Result<u128, DispatchError> {
+        let amount: u128 = amount.unique_saturated_into();
+
+        match Self::decimals(asset_id) {
+            Some((asset_decimals, erc20_decimals)) if erc20_decimals >= asset_decimals => amount
+                .checked_mul(10u128.pow((erc20_decimals - asset_decimals).into()))
+                .ok_or_else(|| ArithmeticError::Overflow.into()),
+            Some((asset_decimals, erc20_decimals)) => {
+                let scale = 10u128.pow((asset_decimals - erc20_decimals).into());
+                ensure!(amount % scale == 0, Error::<T>::DustAmount);
+                Ok(amount / scale)
+            },
+            None => Ok(amount),
+        }
+    }
+
+    /// Whether any direction of the asset is paused.
+    pub fn is_in_emergency(asset_id: T::AssetId) -> bool {
+        Emergencies::<T>::contains_key(asset_id)
+    }
+
+    /// Whether the direction of the asset is paused.
+    pub fn is_paused(asset_id: T::AssetId, direction: Direction) -> bool {
+        Self::emergencies(asset_id).is_paused(direction)
+    }
+
+    /// The count of assets touched by `pause` and `unpause` of `asset_id`, None for all the
+    /// registered assets.
+    pub fn assets_to_pause(asset_id: Option<T::AssetId>) -> u32 {
+        asset_id.map_or_else(Erc20s::<T>::count, |_| 1)
+    }
+
+    /// The assets in emergency.
+    pub fn paused_assets() -> Vec<T::AssetId> {
+        Emergencies::<T>::iter_keys().collect()
+    }
+
+    /// Set the paused flags of the asset, returns whether the flags are changed.
+    fn set_paused(asset_id: T::AssetId, direction: Option<Direction>, paused: bool) -> bool {
+        Emergencies::<T>::mutate_exists(asset_id, |maybe_flags| {
+            let old = maybe_flags.unwrap_or_default();
+            let mut flags = old;
+            flags.set(direction, paused);
+
+            *maybe_flags = if flags.is_empty() { None } else { Some(flags) };
+
+            flags != old
+        })
+    }
+
+    /// Whether `who` is provided or sufficient, i.e. will be reaped, unlike the accounts only
+    /// kept by the nonce of an evm call. Only these accounts hold native allowances.
+    pub fn can_hold_native_allowances(who: &T::AccountId) -> bool {
+        frame_system::Pallet::<T>::providers(who) > 0 ||
+            frame_system::Pallet::<T>::sufficients(who) > 0
+    }
+
+    pub fn is_in_back_foreign(asset_id: T::AssetId) -> bool {
+        BackForeign::<T>::contains_key(asset_id)
+    }
+
+    /// The assets can back foreign chain.
+    pub fn back_foreign_assets() -> Vec<T::AssetId> {
+        BackForeign::<T>::iter_keys().collect()
+    }
+
+    /// Set the `PRECOMPILE_CODE` under the erc20 precompile address of `asset_id`, unless it has
+    /// a code already.
+    pub(crate) fn set_precompile_code(asset_id: T::AssetId) {
+        let address = T::AssetPrecompileAddress::convert(asset_id);
+        if !pallet_evm::AccountCodes::<T>::contains_key(address) {
+            pallet_evm::AccountCodes::<T>::insert(address, PRECOMPILE_CODE.to_vec());
+        }
+    }
+}
+
+impl<T: Config> Pallet<T>
+where
+    DispatchError: From<<<T as pallet_evm::Config>::Runner as pallet_evm::Runner<T>>::Error>,
+{
+    /// Call `erc20` with `inputs` by `EvmCaller` up to `gas_limit`, return the output and the used
+    /// gas of the call.
+    fn call_evm(
+        erc20: H160,
+        inputs: Vec<u8>,
+        gas_limit: u64,
+    ) -> Result<(Vec<u8>, u64), DispatchError> {
+        let info = T::Runner::call(
+            T::EvmCaller::get(),
+            erc20,
+            inputs,
+            U256::default(),
+            gas_limit,
+            None,
+            None,
+            None,
+            Vec::new(),
+            false,
+            true,
+            T::config(),
+        )
+        .map_err(|e| e.error.into())?;
+
+        match info.exit_reason {
+            ExitReason::Succeed(_) => Ok((info.value, info.used_gas.unique_saturated_into())),
+            reason => {
+                log::warn!(
+                    target: "runtime::assets-bridge",
+                    "call erc20 {:?} failed: {:?}, revert reason: {:?}",
+                    erc20,
+                    reason,
+                    decode_revert_reason(&info.value)
+                        .as_deref()
+                        .map(|r| sp_std::str::from_utf8(r).unwrap_or("<invalid utf8>")),
+                );
+                Err(Self::exit_error(&reason).into())
+            },
+        }
+    }
+
+    /// The distinct error of the failed `exit_reason`.
+    fn exit_error(exit_reason: &ExitReason) -> Error<T> {
+        match exit_reason {
+            ExitReason::Revert(_) => Error::<T>::EvmReverted,
+            ExitReason::Error(ExitError::OutOfGas) => Error::<T>::EvmOutOfGas,
+            ExitReason::Fatal(_) => Error::<T>::EvmFatal,
+            _ => Error::<T>::ExecutedFailed,
+        }
+    }
+
+    /// Call a state-changing function of `erc20` of `asset_id` which returns `bool`, return the
+    /// used gas.
+    /// Tokens returning nothing are accepted, as many erc20s don't follow the standard.
+    fn call_erc20(
+        asset_id: T::AssetId,
+        erc20: H160,
+        inputs: Vec<u8>,
+    ) -> Result<u64, DispatchError> {
+        let (output, used_gas) = Self::call_evm(erc20, inputs, Self::gas_limit(asset_id))?;
+        Self::check_erc20_output(&output)?;
+
+        Ok(used_gas)
+    }
+
+    /// Check the `output` of a state-changing erc20 function which returns `bool`.
+    fn check_erc20_output(output: &[u8]) -> DispatchResult {
+        if output.is_empty() || decode_bool(output) == Some(true) {
+            Ok(())
+        } else {
+            Err(Error::<T>::InvalidReturnData.into())
+        }
+    }
+
+    /// The `decimals()` of `erc20`.
+    pub fn erc20_decimals(erc20: H160) -> Result<u8, DispatchError> {
+        let (output, _) = Self::call_evm(erc20, decimals_encode(), GAS_LIMIT)?;
+        let decimals = decode_uint(&output).ok_or(Error::<T>::InvalidReturnData)?;
+        u8::try_from(decimals).map_err(|_| Error::<T>::InvalidReturnData.into())
+    }
+
+    /// The `totalSupply()` of `erc20`.
+    pub fn erc20_total_supply(erc20: H160) -> Result<U256, DispatchError> {
+        let (output, _) = Self::call_evm(erc20, total_supply_encode(), GAS_LIMIT)?;
+        decode_uint(&output).ok_or_else(|| Error::<T>::InvalidReturnData.into())
+    }
+
+    /// The `balanceOf(account)` of `erc20`.
+    pub fn erc20_balance_of(erc20: H160, account: H160) -> Result<U256, DispatchError> {
+        let (output, _) = Self::call_evm(erc20, balance_of_encode(account), GAS_LIMIT)?;
+        decode_uint(&output).ok_or_else(|| Error::<T>::InvalidReturnData.into())
+    }
+
+    /// Compare the `NetBridged` of `asset_id` with the `totalSupply()` of its erc20 contract by
+    /// a read-only evm call, return `(net_bridged, total_supply)` if the supply exceeds it.
+    /// Note: the erc20 burned outside of the bridge leaves the asset over-backed, which is no
+    /// drift, and the `LockRelease` assets never drift, their erc20 supply is not minted by the
+    /// bridge.
+    pub fn net_bridged_drift(asset_id: T::AssetId) -> Result<Option<(u128, U256)>, DispatchError> {
+        let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::AssetIdHasNotMapped)?;
+        if Self::bridge_modes(asset_id) == BridgeMode::LockRelease {
+            return Ok(None);
+        }
+
+        let total_supply =
+            with_transaction(|| TransactionOutcome::Rollback(Self::erc20_total_supply(erc20)))?;
+        let net_bridged = Self::net_bridged(asset_id);
+
+        if total_supply <= U256::from(net_bridged) {
+            Ok(None)
+        } else {
+            Ok(Some((net_bridged, total_supply)))
+        }
+    }
+
+    /// Start the `NetBridged` of `asset_id` from the `totalSupply()` of `erc20`, which may be
+    /// minted before the registration, by a read-only evm call.
+    fn init_net_bridged(asset_id: T::AssetId, erc20: H160) -> DispatchResult {
+        let total_supply =
+            with_transaction(|| TransactionOutcome::Rollback(Self::erc20_total_supply(erc20)))?;
+        let net_bridged = u128::try_from(total_supply).map_err(|_| ArithmeticError::Overflow)?;
+        NetBridged::<T>::insert(asset_id, net_bridged);
+        Ok(())
+    }
+
+    /// Check that the erc20 balance of `eth_address` covers `amount` and the withdraw volume is
+    /// within the limit by read-only calls.
+    /// Note: for the pool validation of `withdraw_signed`, which is fee-free.
+    fn check_withdraw_signed_funds(
+        eth_address: H160,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        let who = Self::sub_accounts(eth_address).ok_or(Error::<T>::AccountIdHasNotMapped)?;
+        let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::AssetIdHasNotMapped)?;
+        let erc20_amount = Self::erc20_amount(asset_id, amount)?;
+
+        with_transaction(|| {
+            let checked = Self::erc20_balance_of(erc20, eth_address).and_then(|balance| {
+                ensure!(balance >= U256::from(erc20_amount), Error::<T>::InsufficientErc20Balance);
+                Self::note_volume(asset_id, Direction::Withdraw, &who, amount)
+            });
+            TransactionOutcome::Rollback(checked)
+        })
+    }
+
+    /// Withdraw erc20 tokens of `evm_account` back to its mapped substrate account, return the
+    /// gas used by the erc20 contract call.
+    /// Note: for `withdraw_signed`, the caller has been authenticated by the signature.
+    #[transactional]
+    pub fn withdraw_from_evm(
+        evm_account: H160,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+    ) -> Result<u64, DispatchError> {
+        let who = Self::check_withdraw_from_evm(evm_account, asset_id, amount)?;
+
+        Self::do_withdraw(&who, evm_account, asset_id, amount, &who)
+    }
+
+    /// Check the withdrawal of `evm_account` without calling the erc20 contract, return the erc20
+    /// contract, the input of its call by `EvmCaller` and the gas limit of the call.
+    /// Note: for the assets-bridge precompile, which must not re-enter the evm by a nested
+    /// `Runner::call`. It calls the erc20 contract in its own evm execution, then calls
+    /// `settle_withdraw_from_evm` with the output.
+    pub fn prepare_withdraw_from_evm(
+        evm_account: H160,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+    ) -> Result<(H160, Vec<u8>, u64), DispatchError> {
+        Self::check_withdraw_from_evm(evm_account, asset_id, amount)?;
+
+        let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
+        let erc20_amount = Self::erc20_amount(asset_id, amount)?;
+
+        Ok((
+            erc20,
+            Self::withdraw_input(asset_id, evm_account, erc20_amount),
+            Self::gas_limit(asset_id),
+        ))
+    }
+
+    /// Settle the withdrawal prepared by `prepare_withdraw_from_evm` after the erc20 contract
+    /// call returned `output`, mint the assets to the mapped substrate account.
+    #[transactional]
+    pub fn settle_withdraw_from_evm(
+        evm_account: H160,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        output: &[u8],
+    ) -> DispatchResult {
+        Self::check_erc20_output(output)?;
+        let who = Self::check_withdraw_from_evm(evm_account, asset_id, amount)?;
+
+        let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
+        let erc20_amount = Self::erc20_amount(asset_id, amount)?;
+        Self::note_withdraw(&who, asset_id, amount, erc20_amount)?;
+
+        Self::mint_withdrawn(evm_account, asset_id, amount, erc20, &who)
+    }
+
+    /// Check the withdrawal of `evm_account`, return its mapped substrate account.
+    fn check_withdraw_from_evm(
+        evm_account: H160,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+    ) -> Result<T::AccountId, DispatchError> {
+        ensure!(!Self::is_paused(asset_id, Direction::Withdraw), Error::<T>::InEmergency);
+        ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
+
+        Self::sub_accounts(evm_account).ok_or_else(|| Error::<T>::AccountIdHasNotMapped.into())
+    }
+
+    /// Bond `asset_id` and `erc20`, record their decimals if they differ.
+    /// `erc20_decimals` should be the `decimals()` of `erc20`, read by a read-only evm call.
+    fn do_register_with_decimals(
+        asset_id: T::AssetId,
+        erc20: H160,
+        erc20_decimals: u8,
+    ) -> DispatchResult {
+        // ensure asset_id and erc20 address has not been mapped
+        ensure!(!Erc20s::<T>::contains_key(asset_id), Error::<T>::AssetIdHasMapped);
+        ensure!(!AssetIds::<T>::contains_key(erc20), Error::<T>::ContractAddressHasMapped);
+
+        let decimals =
+            with_transaction(|| TransactionOutcome::Rollback(Self::erc20_decimals(erc20)))?;
+        ensure!(decimals == erc20_decimals, Error::<T>::Erc20DecimalsMismatch);
+
+        let asset_decimals = pallet_assets::Pallet::<T>::decimals(asset_id);
+        // ensure the scale factor fits in u128
+        ensure!(
+            asset_decimals.abs_diff(erc20_decimals) <= 38,
+            DispatchError::Arithmetic(ArithmeticError::Overflow)
+        );
+
+        Erc20s::<T>::insert(asset_id, erc20);
+        AssetIds::<T>::insert(erc20, asset_id);
+        if asset_decimals != erc20_decimals {
+            Decimals::<T>::insert(asset_id, (asset_decimals, erc20_decimals));
+        }
+
+        Ok(())
+    }
+
+    /// The message signed by the evm address for `withdraw_signed` by `personal_sign`:
+    /// `withdraw:` ++ hex((asset_id, amount, nonce, genesis_hash).encode()).
+    pub fn withdraw_message(asset_id: T::AssetId, amount: T::Balance, nonce: u64) -> Vec<u8> {
+        let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+
+        let mut message = b"withdraw:".to_vec();
+        message.extend((asset_id, amount, nonce, genesis_hash).using_encoded(to_ascii_hex));
+        message
+    }
+
+    /// Check the `withdraw_signed` of `eth_address`, return the nonce of the signature.
+    fn check_withdraw_signed(
+        eth_address: H160,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        eth_signature: &EcdsaSignature,
+    ) -> Result<u64, DispatchError> {
+        ensure!(!Self::is_paused(asset_id, Direction::Withdraw), Error::<T>::InEmergency);
+        ensure!(!amount.is_zero(), Error::<T>::ZeroBalance);
+        ensure!(Erc20s::<T>::contains_key(asset_id), Error::<T>::AssetIdHasNotMapped);
+        ensure!(SubAccounts::<T>::contains_key(eth_address), Error::<T>::AccountIdHasNotMapped);
+
+        let nonce = Self::withdraw_nonces(eth_address);
+        let address =
+            eth_recover(eth_signature, &Self::withdraw_message(asset_id, amount, nonce), &[][..])
+                .ok_or(Error::<T>::BadSignature)?;
+        ensure!(eth_address == address, Error::<T>::InvalidSignature);
+
+        Ok(nonce)
+    }
+
+    /// Record `amount` of `who` into the volumes of the current window.
+    /// Fail if the total or the per-account volume exceeds the limit, the transfer that
+    /// reaches a limit emits `VolumeLimitReached`.
+    fn note_volume(
+        asset_id: T::AssetId,
+        direction: Direction,
+        who: &T::AccountId,
+        amount: T::Balance,
+    ) -> DispatchResult {
+        let limit = match Self::volume_limits(asset_id, direction) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+        let now = frame_system::Pallet::<T>::block_number();
+
+        // returns whether the volume reaches the max
+        let note =
+            |window: &mut (T::BlockNumber, T::Balance), max| -> Result<bool, DispatchError> {
+                let (window_start, volume) = window;
+                if now.saturating_sub(*window_start) >= limit.window {
+                    *window_start = now;
+                    *volume = Zero::zero();
+                }
+
+                *volume = volume.saturating_add(amount);
+                ensure!(*volume <= max, Error::<T>::VolumeLimitExceeded);
+
+                Ok(*volume == max)
+            };
+
+        if let Some(max) = limit.total {
+            if TotalVolumes::<T>::try_mutate(asset_id, direction, |v| note(v, max))? {
+                Self::deposit_event(Event::VolumeLimitReached(asset_id, direction, None));
+            }
+        }
+
+        if let Some(max) = limit.per_account {
+            if AccountVolumes::<T>::try_mutate((asset_id, direction, who), |v| note(v, max))? {
+                Self::deposit_event(Event::VolumeLimitReached(
+                    asset_id,
+                    direction,
+                    Some(who.clone()),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove `amount` of `who` noted at `noted_at` from the volumes, unless the window has
+    /// restarted since then.
+    fn unnote_volume(
+        asset_id: T::AssetId,
+        direction: Direction,
+        who: &T::AccountId,
+        amount: T::Balance,
+        noted_at: T::BlockNumber,
+    ) {
+        let limit = match Self::volume_limits(asset_id, direction) {
+            Some(limit) => limit,
+            None => return,
+        };
+        let now = frame_system::Pallet::<T>::block_number();
+
+        let unnote = |window: &mut (T::BlockNumber, T::Balance)| {
+            let (window_start, volume) = window;
+            if *window_start <= noted_at && now.saturating_sub(*window_start) < limit.window {
+                *volume = volume.saturating_sub(amount);
+            }
+        };
+
+        if TotalVolumes::<T>::contains_key(asset_id, direction) {
+            TotalVolumes::<T>::mutate(asset_id, direction, unnote);
+        }
+        if AccountVolumes::<T>::contains_key((asset_id, direction, who)) {
+            AccountVolumes::<T>::mutate((asset_id, direction, who), unnote);
+        }
+    }
+
+    fn do_claim_account(who: T::AccountId, eth_address: H160) -> DispatchResult {
+        <T as pallet_assets::Config>::Currency::reserve(&who, T::ClaimBond::get())?;
+
+        // Before the balance moves: reaping the unclaimed account resets its nonce.
+        Self::keep_evm_nonce(&T::UnclaimedAddressMapping::into_account_id(eth_address), &who);
+        Self::move_unclaimed_balance(eth_address, &who)?;
+
+        SubAccounts::<T>::insert(eth_address, &who);
+        EvmAccounts::<T>::insert(&who, eth_address);
+
+        Self::deposit_event(Event::ClaimAccount(who, eth_address));
+
+        Ok(())
+    }
+
+    /// Move the native balance of the unclaimed account of `eth_address` into `who`, which is
+    /// unreachable from the evm once `eth_address` is claimed.
+    pub(crate) fn move_unclaimed_balance(eth_address: H160, who: &T::AccountId) -> DispatchResult {
+        let unclaimed = T::UnclaimedAddressMapping::into_account_id(eth_address);
+        let balance = <T as pallet_evm::Config>::Currency::free_balance(&unclaimed);
+        if balance.is_zero() || &unclaimed == who {
+            return Ok(());
+        }
+
+        <T as pallet_evm::Config>::Currency::transfer(
+            &unclaimed,
+            who,
+            balance,
+            ExistenceRequirement::AllowDeath,
+        )
+    }
+
+    /// Raise the nonce of `to` to the one of `from`, when an evm address mapped to `from` is
+    /// mapped to `to`. The evm nonce of the address must not go backwards, or its signed evm
+    /// transactions could be replayed.
+    pub(crate) fn keep_evm_nonce(from: &T::AccountId, to: &T::AccountId) {
+        let nonce = frame_system::Pallet::<T>::account_nonce(from);
+        if frame_system::Pallet::<T>::account_nonce(to) < nonce {
+            frame_system::Account::<T>::mutate(to, |account| account.nonce = nonce);
+        }
+    }
+
+    fn create_evm(init: Vec<u8>) -> Result<H160, DispatchError> {
+        let info = T::Runner::create(
+            T::EvmCaller::get(),
+            init,
+            U256::default(),
+            CREATE_GAS_LIMIT,
+            None,
+            None,
+            None,
+            Vec::new(),
+            false,
+            true,
+            T::config(),
+        )
+        .map_err(|e| e.error.into())?;
+
+        match info.exit_reason {
+            ExitReason::Succeed(_) => Ok(info.value),
+            reason => {
+                log::warn!(target: "runtime::assets-bridge", "create erc20 failed: {:?}", reason);
+                Err(Self::exit_error(&reason).into())
+            },
+        }
+    }
+
+    /// Burn `amount` of `asset_id` from `who` and mint or release the erc20 into `recipient`,
+    /// return the gas used by the erc20 contract call.
+    fn do_deposit(
+        who: &T::AccountId,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        recipient: H160,
+    ) -> Result<u64, DispatchError> {
+        Self::note_volume(asset_id, Direction::Deposit, who, amount)?;
+
+        // 1. burn asset
+        let _ = pallet_assets::Pallet::<T>::burn_from(asset_id, who, amount)?;
+
+        // 2. mint or release erc20
+        let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
+        let erc20_amount = Self::erc20_amount(asset_id, amount)?;
+
+        let inputs = match Self::bridge_modes(asset_id) {
+            BridgeMode::MintBurn => {
+                NetBridged::<T>::try_mutate(asset_id, |net| -> DispatchResult {
+                    *net = net.checked_add(erc20_amount).ok_or(ArithmeticError::Overflow)?;
+                    Ok(())
+                })?;
+                mint_into_encode(recipient, erc20_amount)
+            },
+            BridgeMode::LockRelease => {
+                EscrowBalances::<T>::try_mutate(asset_id, |escrow| -> DispatchResult {
+                    *escrow =
+                        escrow.checked_sub(erc20_amount).ok_or(Error::<T>::InsufficientEscrow)?;
+                    Ok(())
+                })?;
+                transfer_encode(recipient, erc20_amount)
+            },
+        };
+
+        let used_gas = Self::call_erc20(asset_id, erc20, inputs)?;
+
+        Self::deposit_event(Event::DepositExecuted(
+            asset_id,
+            who.clone(),
+            recipient,
+            amount,
+            erc20,
+        ));
+
+        Ok(used_gas)
+    }
+
+    /// Burn or lock `amount` of the erc20 from `evm_account` of `who` and mint `asset_id` into
+    /// `dest`, return the gas used by the erc20 contract call.
+    fn do_withdraw(
+        who: &T::AccountId,
+        evm_account: H160,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        dest: &T::AccountId,
+    ) -> Result<u64, DispatchError> {
+        // 1. burn or lock erc20
+        let erc20 = Self::erc20s(asset_id).ok_or(Error::<T>::ContractAddressHasNotMapped)?;
+        let erc20_amount = Self::erc20_amount(asset_id, amount)?;
+
+        Self::note_withdraw(who, asset_id, amount, erc20_amount)?;
+
+        let inputs = Self::withdraw_input(asset_id, evm_account, erc20_amount);
+        let used_gas = Self::call_erc20(asset_id, erc20, inputs)?;
+
+        // 2. mint asset
+        Self::mint_withdrawn(evm_account, asset_id, amount, erc20, dest)?;
+
+        Ok(used_gas)
+    }
+
+    /// Note the volume of the withdrawal of `who`, and the `erc20_amount` burned from or locked
+    /// in the erc20 contract of `asset_id`.
+    fn note_withdraw(
+        who: &T::AccountId,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        erc20_amount: u128,
+    ) -> DispatchResult {
+        Self::note_volume(asset_id, Direction::Withdraw, who, amount)?;
+
+        match Self::bridge_modes(asset_id) {
+            BridgeMode::MintBurn => {
+                // the drift is reported by `report_drift` instead of blocking the withdrawal
+                NetBridged::<T>::mutate(asset_id, |net| *net = net.saturating_sub(erc20_amount));
+                Ok(())
+            },
+            BridgeMode::LockRelease =>
+                EscrowBalances::<T>::try_mutate(asset_id, |escrow| -> DispatchResult {
+                    *escrow = escrow.checked_add(erc20_amount).ok_or(ArithmeticError::Overflow)?;
+                    Ok(())
+                }),
+        }
+    }
+
+    /// The input of the erc20 call burning or locking `erc20_amount` of `evm_account`.
+    fn withdraw_input(asset_id: T::AssetId, evm_account: H160, erc20_amount: u128) -> Vec<u8> {
+        match Self::bridge_modes(asset_id) {
+            BridgeMode::MintBurn => burn_from_encode(evm_account, erc20_amount),
+            BridgeMode::LockRelease =>
+                transfer_from_encode(evm_account, T::EvmCaller::get(), erc20_amount),
+        }
+    }
+
+    /// Mint the withdrawn `amount` of `asset_id` into `dest`.
+    fn mint_withdrawn(
+        evm_account: H160,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        erc20: H160,
+        dest: &T::AccountId,
+    ) -> DispatchResult {
+        pallet_assets::Pallet::<T>::mint_into(asset_id, dest, amount)?;
+
+        Self::deposit_event(Event::WithdrawExecuted(
+            asset_id,
+            dest.clone(),
+            evm_account,
+            amount,
+            erc20,
+        ));
+
+        Ok(())
+    }
+
+    /// The post dispatch info of the bridge calls of `who`, charging the `used_gas` of the erc20
+    /// contract call on top of the `base` weight.
+    fn post_info(base: Weight, used_gas: u64, who: &T::AccountId) -> PostDispatchInfo {
+        PostDispatchInfo {
+            actual_weight: Some(base.saturating_add(Self::used_gas_weight(used_gas))),
+            pays_fee: Self::pays_fee(who),
+        }
+    }
+
+    /// The actual weight of the inner erc20 contract call which used `used_gas`, keeping the
+    /// `GasLimits` read of `evm_call_weight`.
+    fn used_gas_weight(used_gas: u64) -> Weight {
+        Self::gas_weight(used_gas).saturating_add(T::DbWeight::get().reads(1))
+    }
+
+    /// Check `dest` by the `DestFormats` of `asset_id`.
+    ///
+    /// Returns the beneficiary location of `dest` for the xcm transfers, None for `Raw`.
+    fn check_dest(
+        asset_id: T::AssetId,
+        dest: &BackForeignDest,
+    ) -> Result<Option<MultiLocation>, DispatchError> {
+        use xcm::latest::{Junction, Junctions::X1, NetworkId};
+
+        let beneficiary = match Self::dest_formats(asset_id) {
+            DestFormat::AccountId32 => {
+                let id =
+                    <[u8; 32]>::try_from(dest.as_slice()).map_err(|_| Error::<T>::InvalidDest)?;
+                Some(Junction::AccountId32 { network: NetworkId::Any, id })
+            },
+            DestFormat::AccountKey20 => {
+                let key =
+                    <[u8; 20]>::try_from(dest.as_slice()).map_err(|_| Error::<T>::InvalidDest)?;
+                Some(Junction::AccountKey20 { network: NetworkId::Any, key })
+            },
+            DestFormat::Raw => {
+                ensure!(!dest.is_empty(), Error::<T>::InvalidDest);
+                None
+            },
+        };
+
+        Ok(beneficiary.map(|junction| MultiLocation::new(0, X1(junction))))
+    }
+
+    /// Burn `amount` of `asset_id` from `who` and transfer it back to `dest` on the foreign
+    /// chain, by xcm to `beneficiary` if the asset has a reserve location, otherwise by the
+    /// relayer.
+    fn do_back_foreign(
+        who: &T::AccountId,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        dest: BackForeignDest,
+        beneficiary: Option<MultiLocation>,
+    ) -> DispatchResult {
+        // ensure asset_id registered in back_foreign list
+        ensure!(Self::is_in_back_foreign(asset_id), Error::<T>::BanBackForeign);
+        ensure!(!Self::is_paused(asset_id, Direction::BackForeign), Error::<T>::InEmergency);
+
+        Self::note_volume(asset_id, Direction::BackForeign, who, amount)?;
+
+        // burn asset first, then relay will transfer back `dest`.
+        let _ = pallet_assets::Pallet::<T>::burn_from(asset_id, who, amount)?;
+
+        if let Some(location) = Self::reserve_locations(asset_id) {
+            let beneficiary = beneficiary.ok_or(Error::<T>::InvalidDest)?;
+            Self::send_back_foreign_xcm(who, asset_id, amount, location, beneficiary)
+        } else {
+            Self::queue_back_foreign(who, asset_id, amount, dest);
+            Ok(())
+        }
+    }
+
+    /// Track the back foreign transfer of the burned `amount` of `asset_id` from `who`.
+    fn queue_back_foreign(
+        who: &T::AccountId,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        dest: BackForeignDest,
+    ) {
+        let nonce = BackForeignNonce::<T>::mutate(|nonce| {
+            let current = *nonce;
+            *nonce = nonce.saturating_add(1);
+            current
+        });
+        let deadline =
+            frame_system::Pallet::<T>::block_number().saturating_add(T::BackForeignTimeout::get());
+
+        PendingBackForeign::<T>::insert(
+            nonce,
+            BackForeignTransfer {
+                who: who.clone(),
+                asset_id,
+                amount,
+                dest: dest.clone(),
+                deadline,
+                status: BackForeignStatus::Pending,
+            },
+        );
+
+        Self::deposit_event(Event::BackForeignQueued(nonce, who.clone(), asset_id, amount, dest));
+    }
+
+    /// Send the burned `amount` of `asset_id` at `location` back to `who` on its reserve chain.
+    ///
+    /// The reserve chain withdraws the asset from the sovereign account of this chain, buys the
+    /// execution with it and deposits the rest into `beneficiary`, the same as
+    /// `InitiateReserveWithdraw`.
+    fn send_back_foreign_xcm(
+        who: &T::AccountId,
+        asset_id: T::AssetId,
+        amount: T::Balance,
+        location: MultiLocation,
+        beneficiary: MultiLocation,
+    ) -> DispatchResult {
+        use xcm::latest::prelude::*;
+
+        // the reserve chain and the asset location in it
+        let (reserve, asset_location) = match location.first_interior() {
+            Some(Parachain(id)) => {
+                let reserve = MultiLocation::new(location.parents, X1(Parachain(*id)));
+                (reserve, MultiLocation::new(0, location.interior.split_first().0))
+            },
+            _ => (
+                MultiLocation::new(location.parents, Here),
+                MultiLocation::new(0, location.interior),
+            ),
+        };
+
+        let asset: MultiAsset =
+            (asset_location, UniqueSaturatedInto::<u128>::unique_saturated_into(amount)).into();
+        let message = Xcm(vec![
+            WithdrawAsset(asset.clone().into()),
+            ClearOrigin,
+            BuyExecution { fees: asset, weight_limit: Unlimited },
+            DepositAsset { assets: Wild(All), max_assets: 1, beneficiary: beneficiary.clone() },
+        ]);
+
+        T::XcmSender::send_xcm(reserve.clone(), message).map_err(|e| {
+            log::warn!(
+                target: "runtime::assets-bridge",
+                "send back foreign xcm to {:?} failed: {:?}",
+                reserve,
+                e
+            );
+            Error::<T>::XcmSendFailed
+        })?;
+
+        Self::deposit_event(Event::BackForeignXcmSent(
+            who.clone(),
+            asset_id,
+            amount,
+            reserve,
+            beneficiary,
+        ));
+
+        Ok(())
+    }
+
+    /// Finish the pending back foreign transfer of `nonce` with the final `status`, it is kept in
+    /// `PendingBackForeign` for `BackForeignRetention` blocks and returned.
+    fn finish_back_foreign(
+        nonce: u64,
+        status: BackForeignStatus,
+    ) -> Result<
+        BackForeignTransfer<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>,
+        DispatchError,
+    > {
+        let mut transfer =
+            PendingBackForeign::<T>::get(nonce).ok_or(Error::<T>::BackForeignNotFound)?;
+        ensure!(transfer.status == BackForeignStatus::Pending, Error::<T>::BackForeignNotPending);
+        transfer.status = status;
+
+        // removed at the start of the block after the retention
+        let expiry = frame_system::Pallet::<T>::block_number()
+            .saturating_add(T::BackForeignRetention::get())
+            .saturating_add(One::one());
+        BackForeignExpiries::<T>::try_append(expiry, nonce)
+            .map_err(|_| Error::<T>::TooManyFinishedBackForeign)?;
+        PendingBackForeign::<T>::insert(nonce, transfer.clone());
+
+        Ok(transfer)
+    }
+
+    /// Mint the burned asset of the refunded or reclaimed back foreign `transfer` back to its
+    /// owner, and remove its amount from the back foreign volumes.
+    fn cancel_back_foreign(
+        transfer: &BackForeignTransfer<T::AccountId, T::AssetId, T::Balance, T::BlockNumber>,
+    ) -> DispatchResult {
+        pallet_assets::Pallet::<T>::mint_into(transfer.asset_id, &transfer.who, transfer.amount)?;
+
+        // the volume is noted when the transfer is queued, `BackForeignTimeout` before the deadline
+        let queued_at = transfer.deadline.saturating_sub(T::BackForeignTimeout::get());
+        Self::unnote_volume(
+            transfer.asset_id,
+            Direction::BackForeign,
+            &transfer.who,
+            transfer.amount,
+            queued_at,
+        );
+
+        Ok(())
+    }
+
+    /// Consume one fee-free call of `who`.
+    /// The fee is waived only within the quota of the current period, failed calls always pay.
+    fn pays_fee(who: &T::AccountId) -> Pays {
+        let now = frame_system::Pallet::<T>::block_number();
+
+        FreeCallsUsed::<T>::mutate(who, |(period_start, used)| {
+            if now.saturating_sub(*period_start) >= T::FreeCallsPeriod::get() {
+                *period_start = now;
+                *used = 0;
+            }
+
+            if *used < T::FreeCalls::get() {
+                *used += 1;
+                Pays::No
+            } else {
+                Pays::Yes
+            }
+        })
     }
 }
@@ -0,0 +1,537 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations for the assets bridge pallet.
+
+use super::*;
+use frame_support::{pallet_prelude::*, storage_alias, traits::OnRuntimeUpgrade};
+
+pub mod v1 {
+    use super::*;
+
+    /// The `Emergencies` before v1: all the directions of the assets are paused.
+    #[storage_alias]
+    pub type Emergencies<T: Config> =
+        StorageValue<Pallet<T>, Vec<<T as Config>::AssetId>, ValueQuery>;
+
+    /// Migrate the `Emergencies` from `Vec<AssetId>` to `map AssetId => PauseFlags`.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            if on_chain_version >= 1 {
+                log::info!(
+                    target: "runtime::assets-bridge",
+                    "skip migration v1, on chain version is {:?}",
+                    on_chain_version
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let emergencies = Emergencies::<T>::take();
+            let count = emergencies.len() as u64;
+            for asset_id in emergencies {
+                crate::Emergencies::<T>::insert(asset_id, PauseFlags::all());
+            }
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            log::info!(
+                target: "runtime::assets-bridge",
+                "migrated {} emergencies to v1",
+                count
+            );
+
+            T::DbWeight::get().reads_writes(2, count.saturating_add(2))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok(Emergencies::<T>::get().encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let emergencies = Vec::<T::AssetId>::decode(&mut &state[..])
+                .map_err(|_| "the state of pre_upgrade should be decodable")?;
+
+            for asset_id in emergencies.iter() {
+                ensure!(
+                    crate::Emergencies::<T>::get(asset_id) == PauseFlags::all(),
+                    "the emergency asset should be paused in all directions"
+                );
+            }
+            ensure!(
+                crate::Emergencies::<T>::iter_keys().count() == emergencies.len(),
+                "the count of emergencies should be unchanged"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 1,
+                "the storage version should be at least 1"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v2 {
+    use super::*;
+
+    /// The `BackForeign` before v2.
+    #[storage_alias]
+    pub type BackForeign<T: Config> =
+        StorageValue<Pallet<T>, Vec<<T as Config>::AssetId>, ValueQuery>;
+
+    /// Migrate the `BackForeign` from `Vec<AssetId>` to `map AssetId => ()`.
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            if on_chain_version >= 2 {
+                log::info!(
+                    target: "runtime::assets-bridge",
+                    "skip migration v2, on chain version is {:?}",
+                    on_chain_version
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let back_foreign = BackForeign::<T>::take();
+            let count = back_foreign.len() as u64;
+            for asset_id in back_foreign {
+                crate::BackForeign::<T>::insert(asset_id, ());
+            }
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            log::info!(
+                target: "runtime::assets-bridge",
+                "migrated {} back foreign assets to v2",
+                count
+            );
+
+            T::DbWeight::get().reads_writes(2, count.saturating_add(2))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok(BackForeign::<T>::get().encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let back_foreign = Vec::<T::AssetId>::decode(&mut &state[..])
+                .map_err(|_| "the state of pre_upgrade should be decodable")?;
+
+            for asset_id in back_foreign.iter() {
+                ensure!(
+                    crate::BackForeign::<T>::contains_key(asset_id),
+                    "the back foreign asset should be migrated"
+                );
+            }
+            ensure!(
+                crate::BackForeign::<T>::iter_keys().count() == back_foreign.len(),
+                "the count of back foreign assets should be unchanged"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 2,
+                "the storage version should be at least 2"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v3 {
+    use super::*;
+
+    /// Move the native balances of the unclaimed accounts of the claimed evm addresses into
+    /// their `SubAccounts` and keep their evm nonces, since v3 the claimed addresses are mapped
+    /// by `ClaimedAddressMapping`.
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            if on_chain_version >= 3 {
+                log::info!(
+                    target: "runtime::assets-bridge",
+                    "skip migration v3, on chain version is {:?}",
+                    on_chain_version
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut count = 0u64;
+            let mut moved = 0u64;
+            for (eth_address, who) in SubAccounts::<T>::iter() {
+                count = count.saturating_add(1);
+                Pallet::<T>::keep_evm_nonce(
+                    &T::UnclaimedAddressMapping::into_account_id(eth_address),
+                    &who,
+                );
+                match Pallet::<T>::move_unclaimed_balance(eth_address, &who) {
+                    Ok(()) => moved = moved.saturating_add(1),
+                    Err(e) => log::warn!(
+                        target: "runtime::assets-bridge",
+                        "failed to move the unclaimed balance of {:?}: {:?}",
+                        eth_address,
+                        e
+                    ),
+                }
+            }
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+
+            log::info!(
+                target: "runtime::assets-bridge",
+                "migrated the unclaimed balances of {} of {} claimed accounts to v3",
+                moved,
+                count
+            );
+
+            T::DbWeight::get().reads_writes(
+                count.saturating_mul(3).saturating_add(1),
+                count.saturating_mul(3).saturating_add(1),
+            )
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((SubAccounts::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let count = u32::decode(&mut &state[..])
+                .map_err(|_| "the state of pre_upgrade should be decodable")?;
+
+            ensure!(
+                SubAccounts::<T>::iter_keys().count() as u32 == count,
+                "the count of claimed accounts should be unchanged"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 3,
+                "the storage version should be at least 3"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v4 {
+    use super::*;
+
+    /// Initialize the `NetBridged` of the `MintBurn` assets by the `totalSupply()` of their erc20
+    /// contracts, since v4 it is updated on every `deposit` and `withdraw`.
+    pub struct MigrateToV4<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T>
+    where
+        DispatchError: From<<<T as pallet_evm::Config>::Runner as pallet_evm::Runner<T>>::Error>,
+    {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            if on_chain_version >= 4 {
+                log::info!(
+                    target: "runtime::assets-bridge",
+                    "skip migration v4, on chain version is {:?}",
+                    on_chain_version
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut count = 0u64;
+            let mut migrated = 0u64;
+            for (asset_id, erc20) in Erc20s::<T>::iter() {
+                count = count.saturating_add(1);
+                if Pallet::<T>::bridge_modes(asset_id) == BridgeMode::LockRelease {
+                    continue;
+                }
+
+                let total_supply = with_transaction(|| {
+                    TransactionOutcome::Rollback(Pallet::<T>::erc20_total_supply(erc20))
+                });
+                match total_supply {
+                    Ok(total_supply) => {
+                        NetBridged::<T>::insert(
+                            asset_id,
+                            u128::try_from(total_supply).unwrap_or(u128::MAX),
+                        );
+                        migrated = migrated.saturating_add(1);
+                    },
+                    Err(e) => log::warn!(
+                        target: "runtime::assets-bridge",
+                        "failed to read the total supply of {:?}: {:?}",
+                        erc20,
+                        e
+                    ),
+                }
+            }
+
+            StorageVersion::new(4).put::<Pallet<T>>();
+
+            log::info!(
+                target: "runtime::assets-bridge",
+                "migrated the net bridged of {} of {} assets to v4",
+                migrated,
+                count
+            );
+
+            T::DbWeight::get()
+                .reads_writes(count.saturating_mul(2).saturating_add(1), migrated.saturating_add(1))
+                .saturating_add(Pallet::<T>::gas_weight(GAS_LIMIT).saturating_mul(migrated))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Erc20s::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let count = u32::decode(&mut &state[..])
+                .map_err(|_| "the state of pre_upgrade should be decodable")?;
+
+            ensure!(
+                Erc20s::<T>::iter_keys().count() as u32 == count,
+                "the count of registered assets should be unchanged"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 4,
+                "the storage version should be at least 4"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v5 {
+    use super::*;
+
+    /// The `Erc20s` before v5, without the counter.
+    #[storage_alias]
+    pub type Erc20s<T: Config> =
+        StorageMap<Pallet<T>, Twox64Concat, <T as Config>::AssetId, H160, OptionQuery>;
+
+    /// Initialize the counter of `Erc20s`, since v5 it weighs pausing and unpausing all the
+    /// assets.
+    pub struct MigrateToV5<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            if on_chain_version >= 5 {
+                log::info!(
+                    target: "runtime::assets-bridge",
+                    "skip migration v5, on chain version is {:?}",
+                    on_chain_version
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let count = crate::Erc20s::<T>::initialize_counter();
+
+            StorageVersion::new(5).put::<Pallet<T>>();
+
+            log::info!(
+                target: "runtime::assets-bridge",
+                "migrated the counter of {} registered assets to v5",
+                count
+            );
+
+            T::DbWeight::get().reads_writes((count as u64).saturating_add(1), 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            Ok((Erc20s::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let count = u32::decode(&mut &state[..])
+                .map_err(|_| "the state of pre_upgrade should be decodable")?;
+
+            ensure!(
+                crate::Erc20s::<T>::count() == count,
+                "the counter of registered assets should be initialized"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 5,
+                "the storage version should be at least 5"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v6 {
+    use super::*;
+
+    /// Set the `PRECOMPILE_CODE` of the assets created before v6, since v6 it is set when an
+    /// asset is created rather than registered.
+    pub struct MigrateToV6<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            if on_chain_version >= 6 {
+                log::info!(
+                    target: "runtime::assets-bridge",
+                    "skip migration v6, on chain version is {:?}",
+                    on_chain_version
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut count = 0u64;
+            for asset_id in pallet_assets::Pallet::<T>::asset_ids() {
+                count = count.saturating_add(1);
+                Pallet::<T>::set_precompile_code(asset_id);
+            }
+
+            StorageVersion::new(6).put::<Pallet<T>>();
+
+            log::info!(
+                target: "runtime::assets-bridge",
+                "migrated the precompile code of {} assets to v6",
+                count
+            );
+
+            T::DbWeight::get()
+                .reads_writes(count.saturating_mul(2).saturating_add(1), count.saturating_add(1))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+            for asset_id in pallet_assets::Pallet::<T>::asset_ids() {
+                ensure!(
+                    pallet_evm::AccountCodes::<T>::contains_key(
+                        T::AssetPrecompileAddress::convert(asset_id)
+                    ),
+                    "the erc20 precompile of every asset should have a code"
+                );
+            }
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 6,
+                "the storage version should be at least 6"
+            );
+
+            Ok(())
+        }
+    }
+}
+
+pub mod v7 {
+    use super::*;
+    use pallet_evm::AddressMapping;
+
+    /// The allowances of the native erc20 precompile before v7, kept by the runtime outside of
+    /// any pallet: (owner, spender) => amount.
+    #[storage_alias]
+    pub type Allowances = StorageDoubleMap<
+        NativeErc20,
+        Blake2_128Concat,
+        H160,
+        Blake2_128Concat,
+        H160,
+        u128,
+        ValueQuery,
+    >;
+
+    /// Move the native erc20 `Allowances` into `NativeAllowances`, the owners are mapped by
+    /// `pallet_evm::Config::AddressMapping` and the allowances of the owners not provided are
+    /// dropped.
+    pub struct MigrateToV7<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain_version = Pallet::<T>::on_chain_storage_version();
+            if on_chain_version >= 7 {
+                log::info!(
+                    target: "runtime::assets-bridge",
+                    "skip migration v7, on chain version is {:?}",
+                    on_chain_version
+                );
+                return T::DbWeight::get().reads(1);
+            }
+
+            let (mut migrated, mut dropped) = (0u64, 0u64);
+            for (owner, spender, allowance) in Allowances::drain() {
+                let owner = AddressMappingOf::<T>::into_account_id(owner);
+                if Pallet::<T>::can_hold_native_allowances(&owner) {
+                    migrated = migrated.saturating_add(1);
+                    NativeAllowances::<T>::insert(owner, spender, allowance);
+                } else {
+                    dropped = dropped.saturating_add(1);
+                }
+            }
+
+            StorageVersion::new(7).put::<Pallet<T>>();
+
+            log::info!(
+                target: "runtime::assets-bridge",
+                "migrated {} native allowances to v7, dropped {} of reaped owners",
+                migrated,
+                dropped
+            );
+
+            let count = migrated.saturating_add(dropped);
+            T::DbWeight::get().reads_writes(
+                count.saturating_mul(2).saturating_add(1),
+                count.saturating_add(migrated).saturating_add(1),
+            )
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            let migrated = Allowances::iter()
+                .filter(|(owner, _, _)| {
+                    Pallet::<T>::can_hold_native_allowances(
+                        &AddressMappingOf::<T>::into_account_id(*owner),
+                    )
+                })
+                .count() as u64;
+            Ok(migrated.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            let migrated = u64::decode(&mut &state[..])
+                .map_err(|_| "the state of pre_upgrade should be decodable")?;
+
+            ensure!(Allowances::iter().next().is_none(), "the old allowances should be drained");
+            ensure!(
+                NativeAllowances::<T>::iter().count() as u64 >= migrated,
+                "the native allowances of the provided owners should be migrated"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() >= 7,
+                "the storage version should be at least 7"
+            );
+
+            Ok(())
+        }
+    }
+}
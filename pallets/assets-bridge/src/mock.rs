@@ -21,15 +21,17 @@ pub use assets_bridge::{Config, Error, Event as AssetsBridgeEvent};
 use frame_support::{
     pallet_prelude::Weight,
     parameter_types,
-    traits::{ConstU32, GenesisBuild},
+    traits::{ConstU32, EitherOfDiverse, GenesisBuild},
 };
 use frame_system as system;
 use sp_core::{H160, H256};
 pub use sp_runtime::{
-    testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    testing::{Header, TestXt},
+    traits::{BlakeTwo256, Convert, IdentityLookup},
     AccountId32,
 };
+use std::cell::RefCell;
+use xcm::latest::{prelude::*, Junction};
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -46,7 +48,7 @@ frame_support::construct_runtime!(
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         Evm: pallet_evm::{Pallet, Call, Storage, Config, Event<T>},
         Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
-        AssetsBridge: assets_bridge::{Pallet, Call, Storage, Config<T>, Event<T>},
+        AssetsBridge: assets_bridge::{Pallet, Call, Storage, Config<T>, Event<T>, ValidateUnsigned},
     }
 );
 
@@ -72,7 +74,7 @@ impl system::Config for Test {
     type PalletInfo = PalletInfo;
     type AccountData = pallet_balances::AccountData<u128>;
     type OnNewAccount = ();
-    type OnKilledAccount = ();
+    type OnKilledAccount = AssetsBridge;
     type SystemWeightInfo = ();
     type SS58Prefix = SS58Prefix;
     type OnSetCode = ();
@@ -116,6 +118,13 @@ parameter_types! {
     // 0x1111111111111111111111111111111111111111
     pub EvmCaller: H160 = H160::from_slice(&[17u8;20][..]);
     pub ClaimBond: u128 = 2;
+    pub const FreeCalls: u32 = 2;
+    pub const FreeCallsPeriod: u64 = 10;
+    pub const MaxErc20CodeSize: u32 = 48 * 1024;
+    pub const UnsignedPriority: u64 = 1 << 20;
+    pub const BackForeignTimeout: u64 = 100;
+    pub const BackForeignRetention: u64 = 50;
+    pub const DriftCheckInterval: u64 = 10;
 
     pub const WeightPerGas: Weight = Weight::from_ref_time(20_000);
 }
@@ -135,6 +144,7 @@ impl pallet_assets::Config for Test {
     type WeightInfo = ();
     type Extra = ();
     type AssetAccountDeposit = ();
+    type CallbackHandle = AssetsBridge;
 }
 
 impl pallet_evm::Config for Test {
@@ -143,7 +153,7 @@ impl pallet_evm::Config for Test {
     type WeightPerGas = WeightPerGas;
     type CallOrigin = pallet_evm::EnsureAddressRoot<Self::AccountId>;
     type WithdrawOrigin = pallet_evm::EnsureAddressNever<Self::AccountId>;
-    type AddressMapping = pallet_evm::HashedAddressMapping<BlakeTwo256>;
+    type AddressMapping = assets_bridge::ClaimedAddressMapping<Test>;
     type Currency = Balances;
     type Runner = pallet_evm::runner::stack::Runner<Self>;
     type RuntimeEvent = RuntimeEvent;
@@ -156,14 +166,101 @@ impl pallet_evm::Config for Test {
     type FindAuthor = ();
 }
 
+thread_local! {
+    pub static SENT_XCM: RefCell<Vec<(MultiLocation, Xcm<()>)>> = RefCell::new(Vec::new());
+}
+
+/// The xcm sent to the reserve chains.
+pub(crate) fn sent_xcm() -> Vec<(MultiLocation, Xcm<()>)> {
+    SENT_XCM.with(|q| q.borrow().clone())
+}
+
+/// Records the sent xcm, the parachain 2000 is unreachable.
+pub struct TestSendXcm;
+impl SendXcm for TestSendXcm {
+    fn send_xcm(dest: impl Into<MultiLocation>, msg: Xcm<()>) -> SendResult {
+        let dest = dest.into();
+        if dest == MultiLocation::new(1, X1(Parachain(2000))) {
+            return Err(SendError::CannotReachDestination(dest, msg));
+        }
+        SENT_XCM.with(|q| q.borrow_mut().push((dest, msg)));
+        Ok(())
+    }
+}
+
+pub struct AccountIdToMultiLocation;
+impl Convert<AccountId32, MultiLocation> for AccountIdToMultiLocation {
+    fn convert(account: AccountId32) -> MultiLocation {
+        X1(Junction::AccountId32 { network: NetworkId::Any, id: account.into() }).into()
+    }
+}
+
+pub struct AssetPrecompileAddress;
+impl Convert<u32, H160> for AssetPrecompileAddress {
+    fn convert(asset_id: u32) -> H160 {
+        let mut address = [0u8; 20];
+        address[0..4].copy_from_slice(&[255u8; 4]);
+        address[16..20].copy_from_slice(&asset_id.to_be_bytes());
+        H160(address)
+    }
+}
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+    RuntimeCall: From<C>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = Extrinsic;
+}
+
+pub type AdminOrRoot =
+    EitherOfDiverse<frame_system::EnsureRoot<AccountId32>, assets_bridge::EnsureAdmin<Test>>;
+
 impl assets_bridge::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type EvmCaller = EvmCaller;
     type ClaimBond = ClaimBond;
+    type FreeCalls = FreeCalls;
+    type FreeCallsPeriod = FreeCallsPeriod;
+    type MaxErc20CodeSize = MaxErc20CodeSize;
+    type BackForeignTimeout = BackForeignTimeout;
+    type BackForeignRetention = BackForeignRetention;
+    type XcmSender = TestSendXcm;
+    type AccountIdToMultiLocation = AccountIdToMultiLocation;
+    type AssetPrecompileAddress = AssetPrecompileAddress;
+    type UnclaimedAddressMapping = pallet_evm::HashedAddressMapping<BlakeTwo256>;
+    type UnsignedPriority = UnsignedPriority;
+    type DriftCheckInterval = DriftCheckInterval;
+    type RegisterOrigin = AdminOrRoot;
+    type PauseOrigin = AdminOrRoot;
+    type BackForeignOrigin = AdminOrRoot;
+    type AdminOrigin = frame_system::EnsureRoot<AccountId32>;
+    type WeightInfo = ();
 }
 
 pub const ALICE: [u8; 32] = [1u8; 32];
 pub const BOB: [u8; 32] = [2u8; 32];
+pub const ERC20_1: [u8; 20] = [1u8; 20];
+pub const ERC20_2: [u8; 20] = [2u8; 20];
+
+/// The runtime code of a mock erc20 contract, whose `totalSupply()` returns `total_supply`,
+/// `decimals()` returns `decimals` and any other function returns true.
+pub fn erc20_code(total_supply: u8, decimals: u8) -> Vec<u8> {
+    // PUSH1 0 CALLDATALOAD PUSH1 224 SHR DUP1 PUSH4 totalSupply EQ PUSH1 30 JUMPI
+    // PUSH4 decimals EQ PUSH1 37 JUMPI PUSH1 1 PUSH1 40 JUMP
+    // 30: JUMPDEST POP PUSH1 total_supply PUSH1 40 JUMP
+    // 37: JUMPDEST PUSH1 decimals
+    // 40: JUMPDEST PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+    let mut code = hex_literal::hex!(
+        "60003560e01c806318160ddd14601e5763313ce5671460255760016028565b5060006028565b60005b60005260206000f3"
+    )
+    .to_vec();
+    code[33] = total_supply;
+    code[39] = decimals;
+    code
+}
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
     let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
@@ -179,7 +276,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         .unwrap();
 
     let mut ext = sp_io::TestExternalities::new(t);
-    ext.execute_with(|| System::set_block_number(1));
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        pallet_evm::AccountCodes::<Test>::insert(H160::from_slice(&ERC20_1), erc20_code(0, 0));
+        pallet_evm::AccountCodes::<Test>::insert(H160::from_slice(&ERC20_2), erc20_code(0, 0));
+    });
 
     ext
 }
@@ -56,3 +56,52 @@ pub fn ethereum_signable_message(what: &[u8], extra: &[u8]) -> Vec<u8> {
     v.extend_from_slice(extra);
     v
 }
+
+/// The EIP-712 type of the domain, the name is the pallet name and the salt is the genesis hash.
+pub const EIP712_DOMAIN_TYPE: &[u8] =
+    b"EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)";
+/// The EIP-712 type of the claim message.
+pub const EIP712_CLAIM_TYPE: &[u8] = b"Claim(bytes substrateAccount)";
+/// The EIP-712 version of the domain.
+pub const EIP712_VERSION: &[u8] = b"1";
+
+/// Constructs the EIP-712 domain separator, the `salt` is truncated or right-padded to 32 bytes.
+pub fn eip712_domain_hash(name: &[u8], chain_id: u64, salt: &[u8]) -> [u8; 32] {
+    let mut bytes32 = [0u8; 32];
+    let len = salt.len().min(32);
+    bytes32[..len].copy_from_slice(&salt[..len]);
+
+    let mut v = Vec::with_capacity(32 * 5);
+    v.extend_from_slice(&keccak_256(EIP712_DOMAIN_TYPE)[..]);
+    v.extend_from_slice(&keccak_256(name)[..]);
+    v.extend_from_slice(&keccak_256(EIP712_VERSION)[..]);
+    v.extend_from_slice(&[0u8; 24][..]);
+    v.extend_from_slice(&chain_id.to_be_bytes()[..]);
+    v.extend_from_slice(&bytes32[..]);
+    keccak_256(&v)
+}
+
+/// Constructs the EIP-712 struct hash of the claim message for the encoded `account`.
+pub fn eip712_claim_hash(account: &[u8]) -> [u8; 32] {
+    let mut v = Vec::with_capacity(32 * 2);
+    v.extend_from_slice(&keccak_256(EIP712_CLAIM_TYPE)[..]);
+    v.extend_from_slice(&keccak_256(account)[..]);
+    keccak_256(&v)
+}
+
+/// Attempts to recover the Ethereum address from an EIP-712 typed data signature signed by
+/// using the Ethereum RPC's `eth_signTypedData_v4`.
+pub fn eth_recover_typed(
+    s: &EcdsaSignature,
+    domain_separator: &[u8; 32],
+    struct_hash: &[u8; 32],
+) -> Option<H160> {
+    let mut v = b"\x19\x01".to_vec();
+    v.extend_from_slice(&domain_separator[..]);
+    v.extend_from_slice(&struct_hash[..]);
+    let msg = keccak_256(&v);
+    let mut res = H160::default();
+    res.0
+        .copy_from_slice(&keccak_256(&secp256k1_ecdsa_recover(&s.0, &msg).ok()?[..])[12..]);
+    Some(res)
+}
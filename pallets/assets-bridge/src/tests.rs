@@ -15,8 +15,18 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{mock::*, to_ascii_hex, EcdsaSignature};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    migrations, mock::*, to_ascii_hex, ActionType, AssetRegistration, BackForeignStatus,
+    ClaimedAddressMapping, DestFormat, Direction, EcdsaSignature, PauseFlags, VolumeLimit,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::Pays,
+    pallet_prelude::{InvalidTransaction, TransactionSource, ValidateUnsigned},
+    traits::{Currency, Get, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+    BoundedVec,
+};
+use pallet_evm::AddressMapping;
 use sp_core::{H160, U256};
 
 use ethabi::{Function, Param, ParamType, Token};
@@ -35,8 +45,6 @@ const SIGNATURE: [u8; 65] = hex!["7def4e5806b7cf5dbfa44bc9d14422462dc9fe803c74e5
 const EVM_ADDR: [u8; 20] = hex!["f24ff3a9cf04c71dbc94d0b566f7a27b94566cac"];
 const SUB_ACCOUNT: &str = "5USGSZK3raH3LD4uxvNTa23HN5VULnYrkXonRktyizTJUYg9";
 const PUBKEY: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
-const ERC20_1: [u8; 20] = [1u8; 20];
-const ERC20_2: [u8; 20] = [2u8; 20];
 const MAPPING_ACCOUNT: &str = "5Fghzk1AJt88PeFEzuRfXzbPchiBbsVGTTXcdx599VdZzkTA";
 
 pub fn mint_into_abi() -> Function {
@@ -67,6 +75,27 @@ pub fn burn_from_abi() -> Function {
     }
 }
 
+pub fn transfer_from_abi() -> Function {
+    #[allow(deprecated)]
+    Function {
+        name: "transferFrom".to_owned(),
+        inputs: vec![
+            Param { name: "from".to_owned(), kind: ParamType::Address, internal_type: None },
+            Param { name: "to".to_owned(), kind: ParamType::Address, internal_type: None },
+            Param { name: "amount".to_owned(), kind: ParamType::Uint(256), internal_type: None },
+        ],
+        outputs: vec![],
+        constant: Some(false),
+        state_mutability: Default::default(),
+    }
+}
+
+fn paused_assets() -> Vec<u32> {
+    let mut assets = AssetsBridge::paused_assets();
+    assets.sort();
+    assets
+}
+
 #[test]
 fn evm_address_mapping_substrate_account() {
     use sp_core::Hasher;
@@ -149,6 +178,108 @@ fn burn_from_abi_encode() {
     assert_eq!(encoded2, expected);
 }
 
+#[test]
+fn transfer_abi_encode() {
+    let account = H160::from_slice(&EVM_ADDR);
+    let escrow = EvmCaller::get();
+
+    let expected = hex!("a9059cbb000000000000000000000000f24ff3a9cf04c71dbc94d0b566f7a27b94566cac0000000000000000000000000000000000000000000000000000000005f5e100").to_vec();
+    assert_eq!(crate::transfer_encode(account, 100_000_000u128), expected);
+
+    let transfer_from = transfer_from_abi();
+    let encoded = transfer_from
+        .encode_input(&[
+            Token::Address(account.0.into()),
+            Token::Address(escrow.0.into()),
+            Token::Uint(100_000_000u128.into()),
+        ])
+        .unwrap();
+    assert_eq!(transfer_from.short_signature().to_vec(), hex!("23b872dd").to_vec());
+    assert_eq!(crate::transfer_from_encode(account, escrow, 100_000_000u128), encoded);
+}
+
+#[test]
+fn erc20_constructor_abi_encode() {
+    let expected = ethabi::encode(&[
+        Token::String("Polkadot".to_owned()),
+        Token::String("DOT".to_owned()),
+        Token::Uint(10.into()),
+    ]);
+
+    let encoded = crate::erc20_constructor_encode(b"Polkadot", b"DOT", 10);
+    assert_eq!(encoded, expected);
+
+    let expected = ethabi::encode(&[
+        Token::String("a".repeat(40)),
+        Token::String(String::new()),
+        Token::Uint(18.into()),
+    ]);
+
+    let encoded = crate::erc20_constructor_encode("a".repeat(40).as_bytes(), b"", 18);
+    assert_eq!(encoded, expected);
+}
+
+#[test]
+fn abi_encode_decode_should_work() {
+    use crate::abi::{self, ParamType as AbiType, Token as AbiToken};
+
+    assert_eq!(abi::selector(b"transfer(address,uint256)"), hex!("a9059cbb"));
+    assert_eq!(abi::decimals_encode(), hex!("313ce567").to_vec());
+    assert_eq!(abi::total_supply_encode(), hex!("18160ddd").to_vec());
+    assert_eq!(abi::balance_of_encode(H160::from_slice(&EVM_ADDR))[..4], hex!("70a08231"));
+
+    let account = H160::from_slice(&EVM_ADDR);
+    let tokens = vec![
+        AbiToken::Address(account),
+        AbiToken::Bytes(vec![1u8; 33]),
+        AbiToken::Uint(U256::MAX),
+        AbiToken::String(b"DOT".to_vec()),
+        AbiToken::Bool(true),
+    ];
+    let encoded = abi::encode(&tokens);
+    let expected = ethabi::encode(&[
+        Token::Address(account.0.into()),
+        Token::Bytes(vec![1u8; 33]),
+        Token::Uint(ethabi::Uint::MAX),
+        Token::String("DOT".to_owned()),
+        Token::Bool(true),
+    ]);
+    assert_eq!(encoded, expected);
+
+    let types = [AbiType::Address, AbiType::Bytes, AbiType::Uint, AbiType::String, AbiType::Bool];
+    assert_eq!(abi::decode(&types, &encoded), Some(tokens));
+
+    // out of bounds
+    assert_eq!(abi::decode(&types, &encoded[..encoded.len() - 1]), None);
+    // dirty address padding
+    let mut dirty = encoded.clone();
+    dirty[0] = 1;
+    assert_eq!(abi::decode(&types, &dirty), None);
+    // bool other than 0 or 1
+    let mut dirty = encoded;
+    dirty[159] = 2;
+    assert_eq!(abi::decode(&types, &dirty), None);
+
+    assert_eq!(abi::decode_bool(&ethabi::encode(&[Token::Bool(true)])), Some(true));
+    assert_eq!(abi::decode_uint(&ethabi::encode(&[Token::Uint(18.into())])), Some(18.into()));
+    assert_eq!(abi::decode_uint(&[]), None);
+}
+
+#[test]
+fn abi_decode_revert_reason_should_work() {
+    let mut data = hex!("08c379a0").to_vec();
+    data.extend(ethabi::encode(&[Token::String("ERC20: burn amount exceeds balance".to_owned())]));
+    assert_eq!(
+        crate::decode_revert_reason(&data),
+        Some(b"ERC20: burn amount exceeds balance".to_vec())
+    );
+
+    // not an Error(string)
+    data[0] = 0;
+    assert_eq!(crate::decode_revert_reason(&data), None);
+    assert_eq!(crate::decode_revert_reason(&[]), None);
+}
+
 #[test]
 fn pause_should_work() {
     new_test_ext().execute_with(|| {
@@ -164,8 +295,8 @@ fn pause_should_work() {
             Error::<Test>::EthAddressHasNotMapped
         );
 
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1)));
-        expect_event(AssetsBridgeEvent::Paused(1));
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1), None));
+        expect_event(AssetsBridgeEvent::Paused(1, None));
 
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
@@ -178,7 +309,7 @@ fn pause_should_work() {
 fn pause_should_not_work() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1)),
+            AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1), None),
             Error::<Test>::AssetIdHasNotMapped
         );
 
@@ -190,7 +321,7 @@ fn pause_should_not_work() {
         expect_event(AssetsBridgeEvent::Register(1, H160::from_slice(&ERC20_1)));
 
         assert_noop!(
-            AssetsBridge::pause(RuntimeOrigin::signed(BOB.into()), Some(1)),
+            AssetsBridge::pause(RuntimeOrigin::signed(BOB.into()), Some(1), None),
             Error::<Test>::RequireAdmin
         );
     })
@@ -219,9 +350,9 @@ fn pause_after_pause_should_work() {
         );
 
         // 1. pause(1)
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1)));
-        expect_event(AssetsBridgeEvent::Paused(1));
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1), None));
+        expect_event(AssetsBridgeEvent::Paused(1, None));
+        assert_eq!(paused_assets(), vec![1]);
 
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
@@ -229,21 +360,23 @@ fn pause_after_pause_should_work() {
         );
 
         // 2. pause(1)
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1)));
-        expect_event(AssetsBridgeEvent::Paused(1));
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1), None));
+        expect_event(AssetsBridgeEvent::Paused(1, None));
+        assert_eq!(paused_assets(), vec![1]);
 
-        // 3. pause all
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), None));
-        expect_event(AssetsBridgeEvent::PausedAll);
-        assert_eq!(AssetsBridge::emergencies(), vec![1, 2]);
+        // 3. pause all, weighed by the registered assets
+        assert_eq!(AssetsBridge::assets_to_pause(Some(1)), 1);
+        assert_eq!(AssetsBridge::assets_to_pause(None), 2);
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), None, None));
+        expect_event(AssetsBridgeEvent::PausedAll(None));
+        assert_eq!(paused_assets(), vec![1, 2]);
 
         // 4. pause(2)
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(2)));
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(2), None));
 
         // 5. pause(3)
         assert_noop!(
-            AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(3)),
+            AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(3), None),
             Error::<Test>::AssetIdHasNotMapped
         );
     })
@@ -259,16 +392,16 @@ fn unpause_should_work() {
         ));
         expect_event(AssetsBridgeEvent::Register(1, H160::from_slice(&ERC20_1)));
 
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), None));
-        expect_event(AssetsBridgeEvent::PausedAll);
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), None, None));
+        expect_event(AssetsBridgeEvent::PausedAll(None));
 
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
             Error::<Test>::InEmergency
         );
 
-        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(1)));
-        expect_event(AssetsBridgeEvent::UnPaused(1));
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(1), None));
+        expect_event(AssetsBridgeEvent::UnPaused(1, None));
 
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
@@ -281,7 +414,7 @@ fn unpause_should_work() {
 fn unpause_should_not_work() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(1)),
+            AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(1), None),
             Error::<Test>::AssetIdHasNotMapped
         );
 
@@ -293,7 +426,7 @@ fn unpause_should_not_work() {
         expect_event(AssetsBridgeEvent::Register(1, H160::from_slice(&ERC20_1)));
 
         assert_noop!(
-            AssetsBridge::unpause(RuntimeOrigin::signed(BOB.into()), Some(1)),
+            AssetsBridge::unpause(RuntimeOrigin::signed(BOB.into()), Some(1), None),
             Error::<Test>::RequireAdmin
         );
     })
@@ -316,25 +449,25 @@ fn unpause_after_unpause_should_work() {
         ));
         expect_event(AssetsBridgeEvent::Register(2, H160::from_slice(&ERC20_2)));
 
-        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(1)));
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(1), None));
+        assert!(paused_assets().is_empty());
 
-        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(2)));
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(2), None));
+        assert!(paused_assets().is_empty());
 
         assert_noop!(
-            AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(3)),
+            AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(3), None),
             Error::<Test>::AssetIdHasNotMapped
         );
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
 
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1)));
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1), None));
 
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(2)));
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(2), None));
 
-        assert_eq!(AssetsBridge::emergencies(), vec![1, 2]);
+        assert_eq!(paused_assets(), vec![1, 2]);
 
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
@@ -346,23 +479,23 @@ fn unpause_after_unpause_should_work() {
             Error::<Test>::InEmergency
         );
 
-        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(2)));
-        expect_event(AssetsBridgeEvent::UnPaused(2));
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(2), None));
+        expect_event(AssetsBridgeEvent::UnPaused(2, None));
 
         assert_noop!(
             AssetsBridge::withdraw(RuntimeOrigin::signed(BOB.into()), 2, 1),
             Error::<Test>::EthAddressHasNotMapped
         );
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
-        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), None));
-        expect_event(AssetsBridgeEvent::UnPausedAll);
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), None, None));
+        expect_event(AssetsBridgeEvent::UnPausedAll(None));
 
         assert_noop!(
             AssetsBridge::withdraw(RuntimeOrigin::signed(BOB.into()), 1, 1),
             Error::<Test>::EthAddressHasNotMapped
         );
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
     })
 }
 
@@ -383,7 +516,7 @@ fn more_pause_and_unpause_should_work() {
         ));
         expect_event(AssetsBridgeEvent::Register(2, H160::from_slice(&ERC20_2)));
 
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
 
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
@@ -395,10 +528,10 @@ fn more_pause_and_unpause_should_work() {
             Error::<Test>::EthAddressHasNotMapped
         );
 
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), None));
-        expect_event(AssetsBridgeEvent::PausedAll);
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), None, None));
+        expect_event(AssetsBridgeEvent::PausedAll(None));
 
-        assert_eq!(AssetsBridge::emergencies(), vec![1, 2]);
+        assert_eq!(paused_assets(), vec![1, 2]);
 
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
@@ -410,10 +543,10 @@ fn more_pause_and_unpause_should_work() {
             Error::<Test>::InEmergency
         );
 
-        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(2)));
-        expect_event(AssetsBridgeEvent::UnPaused(2));
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), Some(2), None));
+        expect_event(AssetsBridgeEvent::UnPaused(2, None));
 
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_eq!(paused_assets(), vec![1]);
 
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
@@ -425,15 +558,132 @@ fn more_pause_and_unpause_should_work() {
             Error::<Test>::EthAddressHasNotMapped
         );
 
-        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), None));
-        expect_event(AssetsBridgeEvent::UnPausedAll);
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), None, None));
+        expect_event(AssetsBridgeEvent::UnPausedAll(None));
+
+        assert!(paused_assets().is_empty());
+
+        assert_noop!(
+            AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+    })
+}
+
+#[test]
+fn pause_direction_should_work() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(AssetsBridge::register(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert_ok!(AssetsBridge::back_foreign(RuntimeOrigin::signed(ALICE.into()), 1, false));
+
+        // 1. pause deposit
+        assert_ok!(AssetsBridge::pause(
+            RuntimeOrigin::signed(ALICE.into()),
+            Some(1),
+            Some(Direction::Deposit)
+        ));
+        expect_event(AssetsBridgeEvent::Paused(1, Some(Direction::Deposit)));
+        assert_eq!(
+            AssetsBridge::emergencies(1),
+            PauseFlags { deposit: true, withdraw: false, back_foreign: false }
+        );
+
+        assert_noop!(
+            AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
+            Error::<Test>::InEmergency
+        );
+        assert_noop!(
+            AssetsBridge::withdraw(RuntimeOrigin::signed(BOB.into()), 1, 1),
+            Error::<Test>::EthAddressHasNotMapped
+        );
 
-        assert!(AssetsBridge::emergencies().is_empty());
+        // 2. pause back foreign
+        assert_ok!(AssetsBridge::pause(
+            RuntimeOrigin::signed(ALICE.into()),
+            Some(1),
+            Some(Direction::BackForeign)
+        ));
+        expect_event(AssetsBridgeEvent::Paused(1, Some(Direction::BackForeign)));
+        assert_noop!(
+            AssetsBridge::teleport(
+                RuntimeOrigin::signed(BOB.into()),
+                1,
+                ActionType::BackForeign(1)
+            ),
+            Error::<Test>::InEmergency
+        );
 
+        // 3. unpause deposit, back foreign still paused
+        assert_ok!(AssetsBridge::unpause(
+            RuntimeOrigin::signed(ALICE.into()),
+            Some(1),
+            Some(Direction::Deposit)
+        ));
+        expect_event(AssetsBridgeEvent::UnPaused(1, Some(Direction::Deposit)));
         assert_noop!(
             AssetsBridge::deposit(RuntimeOrigin::signed(BOB.into()), 1, 1),
             Error::<Test>::EthAddressHasNotMapped
         );
+        assert!(AssetsBridge::is_paused(1, Direction::BackForeign));
+        assert_eq!(paused_assets(), vec![1]);
+
+        // 4. pause withdraw of all assets
+        assert_ok!(AssetsBridge::pause(
+            RuntimeOrigin::signed(ALICE.into()),
+            None,
+            Some(Direction::Withdraw)
+        ));
+        expect_event(AssetsBridgeEvent::PausedAll(Some(Direction::Withdraw)));
+        assert_noop!(
+            AssetsBridge::withdraw(RuntimeOrigin::signed(BOB.into()), 1, 1),
+            Error::<Test>::InEmergency
+        );
+
+        // 5. unpause the last directions
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(ALICE.into()), None, None));
+        expect_event(AssetsBridgeEvent::UnPausedAll(None));
+        assert!(paused_assets().is_empty());
+    })
+}
+
+#[test]
+fn migrate_emergencies_to_v1_should_work() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<AssetsBridge>();
+        migrations::v1::Emergencies::<Test>::put(vec![1u32, 2]);
+
+        migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 1);
+        assert!(!migrations::v1::Emergencies::<Test>::exists());
+        assert_eq!(paused_assets(), vec![1, 2]);
+        assert_eq!(AssetsBridge::emergencies(1), PauseFlags::all());
+        assert_eq!(AssetsBridge::emergencies(2), PauseFlags::all());
+        assert!(AssetsBridge::is_paused(2, Direction::BackForeign));
+    })
+}
+
+#[test]
+fn migrate_back_foreign_to_v2_should_work() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(1).put::<AssetsBridge>();
+        migrations::v2::BackForeign::<Test>::put(vec![1u32, 2]);
+
+        migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 2);
+        assert!(!migrations::v2::BackForeign::<Test>::exists());
+        assert!(AssetsBridge::is_in_back_foreign(1));
+        assert!(AssetsBridge::is_in_back_foreign(2));
+        assert!(!AssetsBridge::is_in_back_foreign(3));
+
+        let mut assets = AssetsBridge::back_foreign_assets();
+        assets.sort();
+        assert_eq!(assets, vec![1, 2]);
     })
 }
 
@@ -458,15 +708,1782 @@ fn force_unregister_should_work() {
         assert_eq!(AssetsBridge::erc20s(1), Some(H160::from_slice(&ERC20_1)));
         assert_eq!(AssetsBridge::asset_ids(H160::from_slice(&ERC20_1)), Some(1));
 
-        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), None));
-        expect_event(AssetsBridgeEvent::PausedAll);
-        assert_eq!(AssetsBridge::emergencies(), vec![1]);
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), None, None));
+        expect_event(AssetsBridgeEvent::PausedAll(None));
+        assert_eq!(paused_assets(), vec![1]);
 
         assert_ok!(AssetsBridge::force_unregister(RuntimeOrigin::root(), 1));
         expect_event(AssetsBridgeEvent::ForceUnRegister(1, H160::from_slice(&ERC20_1)));
 
         assert_eq!(AssetsBridge::erc20s(1), None);
         assert_eq!(AssetsBridge::asset_ids(H160::from_slice(&ERC20_1)), None);
-        assert!(AssetsBridge::emergencies().is_empty());
+        assert!(paused_assets().is_empty());
+    })
+}
+
+#[test]
+fn free_calls_should_be_limited() {
+    new_test_ext().execute_with(|| {
+        let teleport = || {
+            AssetsBridge::teleport(
+                RuntimeOrigin::signed(ALICE.into()),
+                10,
+                ActionType::Direct(H160::from_slice(&EVM_ADDR)),
+            )
+            .map(|info| info.pays_fee)
+        };
+
+        // 1. within the quota
+        assert_eq!(teleport(), Ok(Pays::No));
+        assert_eq!(teleport(), Ok(Pays::No));
+        assert_eq!(AssetsBridge::free_calls_used(AccountId32::from(ALICE)), (0, 2));
+
+        // 2. exceed the quota
+        assert_eq!(teleport(), Ok(Pays::Yes));
+
+        // 3. failed calls are not free and do not consume the quota
+        assert_noop!(
+            AssetsBridge::teleport(RuntimeOrigin::signed(BOB.into()), 10, ActionType::FromSubToEth),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+        assert_eq!(AssetsBridge::free_calls_used(AccountId32::from(BOB)), (0, 0));
+
+        // 4. next period
+        System::set_block_number(FreeCallsPeriod::get());
+        assert_eq!(teleport(), Ok(Pays::No));
+        assert_eq!(AssetsBridge::free_calls_used(AccountId32::from(ALICE)), (10, 1));
+    })
+}
+
+#[test]
+fn deposit_to_and_withdraw_to_should_work() {
+    new_test_ext().execute_with(|| {
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE.into()), 1, ALICE.into(), 100));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(ALICE.into()), 1, erc20));
+
+        // 1. deposit into an unmapped evm address
+        assert_ok!(AssetsBridge::deposit_to(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            10,
+            evm_address
+        ));
+        expect_event(AssetsBridgeEvent::DepositExecuted(1, ALICE.into(), evm_address, 10, erc20));
+        assert_eq!(Assets::balance(1, AccountId32::from(ALICE)), 90);
+
+        // 2. withdraw requires the caller's mapped evm address
+        assert_noop!(
+            AssetsBridge::withdraw_to(RuntimeOrigin::signed(BOB.into()), 1, 10, ALICE.into()),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+
+        crate::EvmAccounts::<Test>::insert(AccountId32::from(ALICE), evm_address);
+
+        // 3. withdraw into another substrate account
+        assert_ok!(AssetsBridge::withdraw_to(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            5,
+            BOB.into()
+        ));
+        expect_event(AssetsBridgeEvent::WithdrawExecuted(1, BOB.into(), evm_address, 5, erc20));
+        assert_eq!(Assets::balance(1, AccountId32::from(ALICE)), 90);
+        assert_eq!(Assets::balance(1, AccountId32::from(BOB)), 5);
+
+        // 4. paused
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(ALICE.into()), Some(1), None));
+        assert_noop!(
+            AssetsBridge::deposit_to(RuntimeOrigin::signed(ALICE.into()), 1, 10, evm_address),
+            Error::<Test>::InEmergency
+        );
+        assert_noop!(
+            AssetsBridge::withdraw_to(RuntimeOrigin::signed(ALICE.into()), 1, 5, BOB.into()),
+            Error::<Test>::InEmergency
+        );
+    })
+}
+
+#[test]
+fn withdraw_from_evm_should_work() {
+    new_test_ext().execute_with(|| {
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(ALICE.into()), 1, erc20));
+
+        // 1. the evm address requires a mapped substrate account
+        assert_noop!(
+            AssetsBridge::withdraw_from_evm(evm_address, 1, 10),
+            Error::<Test>::AccountIdHasNotMapped
+        );
+
+        crate::SubAccounts::<Test>::insert(evm_address, AccountId32::from(ALICE));
+
+        // 2. withdraw into the mapped substrate account
+        assert_ok!(AssetsBridge::withdraw_from_evm(evm_address, 1, 10));
+        expect_event(AssetsBridgeEvent::WithdrawExecuted(1, ALICE.into(), evm_address, 10, erc20));
+        assert_eq!(Assets::balance(1, AccountId32::from(ALICE)), 10);
+
+        assert_noop!(
+            AssetsBridge::withdraw_from_evm(evm_address, 1, 0),
+            Error::<Test>::ZeroBalance
+        );
+
+        // 3. paused
+        assert_ok!(AssetsBridge::pause(
+            RuntimeOrigin::signed(ALICE.into()),
+            Some(1),
+            Some(Direction::Withdraw)
+        ));
+        assert_noop!(
+            AssetsBridge::withdraw_from_evm(evm_address, 1, 10),
+            Error::<Test>::InEmergency
+        );
+    })
+}
+
+#[test]
+fn prepare_and_settle_withdraw_from_evm_should_work() {
+    new_test_ext().execute_with(|| {
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(ALICE.into()), 1, erc20));
+        crate::SubAccounts::<Test>::insert(evm_address, AccountId32::from(ALICE));
+        crate::NetBridged::<Test>::insert(1, 100);
+
+        // 1. the erc20 call is left to the precompile, nothing is changed
+        assert_eq!(
+            AssetsBridge::prepare_withdraw_from_evm(evm_address, 1, 10),
+            Ok((erc20, crate::burn_from_encode(evm_address, 10), AssetsBridge::gas_limit(1)))
+        );
+        assert_eq!(AssetsBridge::net_bridged(1), 100);
+        assert_eq!(Assets::balance(1, AccountId32::from(ALICE)), 0);
+
+        // 2. the erc20 call returned false
+        assert_noop!(
+            AssetsBridge::settle_withdraw_from_evm(evm_address, 1, 10, &[0u8; 32]),
+            Error::<Test>::InvalidReturnData
+        );
+
+        // 3. settled after the erc20 call
+        assert_ok!(AssetsBridge::settle_withdraw_from_evm(evm_address, 1, 10, &[]));
+        expect_event(AssetsBridgeEvent::WithdrawExecuted(1, ALICE.into(), evm_address, 10, erc20));
+        assert_eq!(Assets::balance(1, AccountId32::from(ALICE)), 10);
+        assert_eq!(AssetsBridge::net_bridged(1), 90);
+
+        // 4. paused
+        assert_ok!(AssetsBridge::pause(
+            RuntimeOrigin::signed(ALICE.into()),
+            Some(1),
+            Some(Direction::Withdraw)
+        ));
+        assert_noop!(
+            AssetsBridge::prepare_withdraw_from_evm(evm_address, 1, 10),
+            Error::<Test>::InEmergency
+        );
+        assert_noop!(
+            AssetsBridge::settle_withdraw_from_evm(evm_address, 1, 10, &[]),
+            Error::<Test>::InEmergency
+        );
+    })
+}
+
+#[test]
+fn withdraw_signed_should_work() {
+    use sp_io::hashing::keccak_256;
+
+    new_test_ext().execute_with(|| {
+        let erc20 = H160::from_slice(&ERC20_1);
+        let secret = libsecp256k1::SecretKey::parse(&keccak_256(b"bob")).unwrap();
+        let public = libsecp256k1::PublicKey::from_secret_key(&secret);
+        let eth_address = H160::from_slice(&keccak_256(&public.serialize()[1..65])[12..]);
+        let sign = |amount: u128, nonce: u64| {
+            let message = crate::ethereum_signable_message(
+                &AssetsBridge::withdraw_message(1, amount, nonce),
+                &[][..],
+            );
+            let (sig, recovery_id) =
+                libsecp256k1::sign(&libsecp256k1::Message::parse(&keccak_256(&message)), &secret);
+            let mut signature = [0u8; 65];
+            signature[0..64].copy_from_slice(&sig.serialize()[..]);
+            signature[64] = recovery_id.serialize();
+            EcdsaSignature::from_raw(signature)
+        };
+        let call = |amount: u128, eth_signature: EcdsaSignature| {
+            crate::Call::<Test>::withdraw_signed { eth_address, asset_id: 1, amount, eth_signature }
+        };
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(ALICE.into()), 1, erc20));
+        // the erc20 balance is 10: PUSH1 10 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let balance_code = hex!("600a60005260206000f3").to_vec();
+        // the erc20 call returns true: PUSH1 1 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let success_code = hex!("600160005260206000f3").to_vec();
+        pallet_evm::AccountCodes::<Test>::insert(erc20, balance_code.clone());
+
+        // 1. the evm address requires a mapped substrate account
+        assert_eq!(
+            AssetsBridge::validate_unsigned(TransactionSource::External, &call(10, sign(10, 0))),
+            Err(InvalidTransaction::Call.into())
+        );
+
+        crate::SubAccounts::<Test>::insert(eth_address, AccountId32::from(BOB));
+
+        // 2. the signature must match the amount and the nonce
+        assert_eq!(
+            AssetsBridge::validate_unsigned(TransactionSource::External, &call(11, sign(10, 0))),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_eq!(
+            AssetsBridge::validate_unsigned(TransactionSource::External, &call(10, sign(10, 1))),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_ok!(AssetsBridge::validate_unsigned(
+            TransactionSource::External,
+            &call(10, sign(10, 0))
+        ));
+
+        // 3. the pool rejects the withdraw which can't succeed
+        assert_eq!(
+            AssetsBridge::validate_unsigned(TransactionSource::External, &call(11, sign(11, 0))),
+            Err(InvalidTransaction::Call.into())
+        );
+        assert_ok!(AssetsBridge::pause(
+            RuntimeOrigin::signed(ALICE.into()),
+            Some(1),
+            Some(Direction::Withdraw)
+        ));
+        assert_eq!(
+            AssetsBridge::validate_unsigned(TransactionSource::External, &call(10, sign(10, 0))),
+            Err(InvalidTransaction::Call.into())
+        );
+        assert_ok!(AssetsBridge::unpause(
+            RuntimeOrigin::signed(ALICE.into()),
+            Some(1),
+            Some(Direction::Withdraw)
+        ));
+
+        // 4. withdraw into the mapped substrate account
+        pallet_evm::AccountCodes::<Test>::insert(erc20, success_code.clone());
+        assert_noop!(
+            AssetsBridge::withdraw_signed(
+                RuntimeOrigin::signed(BOB.into()),
+                eth_address,
+                1,
+                10,
+                sign(10, 0)
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(AssetsBridge::withdraw_signed(
+            RuntimeOrigin::none(),
+            eth_address,
+            1,
+            10,
+            sign(10, 0)
+        ));
+        expect_event(AssetsBridgeEvent::WithdrawExecuted(1, BOB.into(), eth_address, 10, erc20));
+        assert_eq!(Assets::balance(1, AccountId32::from(BOB)), 10);
+        assert_eq!(AssetsBridge::withdraw_nonces(eth_address), 1);
+
+        // 5. replay
+        assert_eq!(
+            AssetsBridge::validate_unsigned(TransactionSource::External, &call(10, sign(10, 0))),
+            Err(InvalidTransaction::BadProof.into())
+        );
+        assert_noop!(
+            AssetsBridge::withdraw_signed(RuntimeOrigin::none(), eth_address, 1, 10, sign(10, 0)),
+            Error::<Test>::InvalidSignature
+        );
+
+        // 6. the pool rejects the withdraw exceeding the volume limit
+        assert_ok!(AssetsBridge::set_volume_limit(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            Direction::Withdraw,
+            Some(VolumeLimit { window: 10, total: Some(5), per_account: None })
+        ));
+        pallet_evm::AccountCodes::<Test>::insert(erc20, balance_code);
+        assert_eq!(
+            AssetsBridge::validate_unsigned(TransactionSource::External, &call(10, sign(10, 1))),
+            Err(InvalidTransaction::Call.into())
+        );
+        assert_ok!(AssetsBridge::validate_unsigned(
+            TransactionSource::External,
+            &call(5, sign(5, 1))
+        ));
+        assert_eq!(AssetsBridge::total_volumes(1, Direction::Withdraw).1, 0);
+
+        // 7. the failed withdraw consumes the nonce
+        pallet_evm::AccountCodes::<Test>::insert(erc20, success_code);
+        assert_ok!(AssetsBridge::withdraw_signed(
+            RuntimeOrigin::none(),
+            eth_address,
+            1,
+            10,
+            sign(10, 1)
+        ));
+        expect_event(AssetsBridgeEvent::WithdrawSignedFailed(
+            eth_address,
+            1,
+            Error::<Test>::VolumeLimitExceeded.into(),
+        ));
+        assert_eq!(Assets::balance(1, AccountId32::from(BOB)), 10);
+        assert_eq!(AssetsBridge::withdraw_nonces(eth_address), 2);
+    })
+}
+
+#[test]
+fn lock_release_should_work() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(alice.clone()), 1, alice.clone(), 100));
+        crate::SubAccounts::<Test>::insert(evm_address, &alice);
+        crate::EvmAccounts::<Test>::insert(&alice, evm_address);
+
+        // 1. register
+        assert_noop!(
+            AssetsBridge::register_lock_release(RuntimeOrigin::signed(BOB.into()), 1, erc20, 0),
+            Error::<Test>::RequireAdmin
+        );
+        assert_noop!(
+            AssetsBridge::register_lock_release(RuntimeOrigin::signed(alice.clone()), 1, erc20, 6),
+            Error::<Test>::Erc20DecimalsMismatch
+        );
+        assert_ok!(AssetsBridge::register_lock_release(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            erc20,
+            0
+        ));
+        expect_event(AssetsBridgeEvent::Register(1, erc20));
+        assert_eq!(AssetsBridge::bridge_modes(1), crate::BridgeMode::LockRelease);
+
+        // 2. nothing to release
+        assert_noop!(
+            AssetsBridge::deposit(RuntimeOrigin::signed(alice.clone()), 1, 10),
+            Error::<Test>::InsufficientEscrow
+        );
+
+        // 3. withdraw locks the erc20 into the escrow
+        assert_ok!(AssetsBridge::withdraw(RuntimeOrigin::signed(alice.clone()), 1, 10));
+        expect_event(AssetsBridgeEvent::WithdrawExecuted(1, alice.clone(), evm_address, 10, erc20));
+        assert_eq!(AssetsBridge::escrow_balances(1), 10);
+        assert_eq!(Assets::balance(1, &alice), 110);
+
+        // 4. deposit releases the erc20 from the escrow
+        assert_ok!(AssetsBridge::deposit(RuntimeOrigin::signed(alice.clone()), 1, 4));
+        expect_event(AssetsBridgeEvent::DepositExecuted(1, alice.clone(), evm_address, 4, erc20));
+        assert_eq!(AssetsBridge::escrow_balances(1), 6);
+        assert_eq!(Assets::balance(1, &alice), 106);
+
+        assert_noop!(
+            AssetsBridge::deposit(RuntimeOrigin::signed(alice.clone()), 1, 7),
+            Error::<Test>::InsufficientEscrow
+        );
+
+        // 5. unregister keeps the escrow balance
+        assert_ok!(AssetsBridge::force_unregister(RuntimeOrigin::root(), 1));
+        assert_eq!(AssetsBridge::bridge_modes(1), crate::BridgeMode::MintBurn);
+        assert_eq!(AssetsBridge::escrow_balances(1), 6);
+    })
+}
+
+#[test]
+fn registrations_should_work() {
+    new_test_ext().execute_with(|| {
+        assert!(AssetsBridge::registrations().is_empty());
+
+        assert_ok!(AssetsBridge::register(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert_ok!(AssetsBridge::pause(
+            RuntimeOrigin::signed(ALICE.into()),
+            Some(1),
+            Some(Direction::Deposit)
+        ));
+        assert_ok!(AssetsBridge::back_foreign(RuntimeOrigin::signed(ALICE.into()), 1, false));
+
+        assert_eq!(
+            AssetsBridge::registrations(),
+            vec![AssetRegistration {
+                asset_id: 1,
+                erc20: H160::from_slice(&ERC20_1),
+                paused: PauseFlags { deposit: true, ..Default::default() },
+                back_foreign: true,
+            }]
+        );
+    })
+}
+
+#[test]
+fn create_asset_should_set_precompile_code() {
+    use sp_runtime::traits::Convert;
+
+    new_test_ext().execute_with(|| {
+        let precompile_1 = AssetPrecompileAddress::convert(1);
+        let precompile_2 = AssetPrecompileAddress::convert(2);
+        let precompile_3 = AssetPrecompileAddress::convert(3);
+        pallet_evm::AccountCodes::<Test>::insert(precompile_2, vec![0u8]);
+
+        // 1. set for the asset not registered with the bridge
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+        assert_eq!(AssetsBridge::erc20s(1), None);
+        assert_eq!(pallet_evm::AccountCodes::<Test>::get(precompile_1), crate::PRECOMPILE_CODE);
+
+        // 2. the existing code is kept
+        assert_ok!(Assets::create(RuntimeOrigin::signed(ALICE.into()), 2, ALICE.into(), 1));
+        assert_eq!(pallet_evm::AccountCodes::<Test>::get(precompile_2), vec![0u8]);
+
+        // 3. not set by the registration
+        assert_ok!(AssetsBridge::register(
+            RuntimeOrigin::signed(ALICE.into()),
+            3,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert!(!pallet_evm::AccountCodes::<Test>::contains_key(precompile_3));
+    })
+}
+
+#[test]
+fn migrate_precompile_code_to_v6_should_work() {
+    use sp_runtime::traits::Convert;
+
+    new_test_ext().execute_with(|| {
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 2, ALICE.into(), true, 1));
+        // created before v6
+        pallet_evm::AccountCodes::<Test>::remove(AssetPrecompileAddress::convert(1));
+        pallet_evm::AccountCodes::<Test>::remove(AssetPrecompileAddress::convert(2));
+
+        StorageVersion::new(5).put::<AssetsBridge>();
+        migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 6);
+        for asset_id in [1, 2] {
+            assert_eq!(
+                pallet_evm::AccountCodes::<Test>::get(AssetPrecompileAddress::convert(asset_id)),
+                crate::PRECOMPILE_CODE
+            );
+        }
+    })
+}
+
+#[test]
+fn migrate_native_allowances_to_v7_should_work() {
+    new_test_ext().execute_with(|| {
+        let (owner, reaped, spender) =
+            (H160::from([1u8; 20]), H160::from([2u8; 20]), H160::from([3u8; 20]));
+        let owner_account = ClaimedAddressMapping::<Test>::into_account_id(owner);
+        Balances::make_free_balance_be(&owner_account, 10);
+        migrations::v7::Allowances::insert(owner, spender, 5);
+        migrations::v7::Allowances::insert(reaped, spender, 6);
+
+        StorageVersion::new(6).put::<AssetsBridge>();
+        migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 7);
+        assert!(migrations::v7::Allowances::iter().next().is_none());
+        assert_eq!(AssetsBridge::native_allowances(&owner_account, spender), 5);
+        assert_eq!(
+            AssetsBridge::native_allowances(
+                ClaimedAddressMapping::<Test>::into_account_id(reaped),
+                spender
+            ),
+            0
+        );
+        assert_eq!(crate::NativeAllowances::<Test>::iter().count(), 1);
+    })
+}
+
+#[test]
+fn reaped_account_should_remove_native_allowances() {
+    new_test_ext().execute_with(|| {
+        let owner: AccountId32 = [3u8; 32].into();
+        let (spender1, spender2) = (H160::from([1u8; 20]), H160::from([2u8; 20]));
+        Balances::make_free_balance_be(&owner, 10);
+        crate::NativeAllowances::<Test>::insert(&owner, spender1, 5);
+        crate::NativeAllowances::<Test>::insert(&owner, spender2, u128::MAX);
+        crate::NativeAllowances::<Test>::insert(AccountId32::from(ALICE), spender1, 7);
+
+        assert_ok!(<Balances as Currency<_>>::transfer(
+            &owner,
+            &ALICE.into(),
+            10,
+            frame_support::traits::ExistenceRequirement::AllowDeath
+        ));
+
+        assert!(!System::account_exists(&owner));
+        assert_eq!(crate::NativeAllowances::<Test>::iter_prefix(&owner).count(), 0);
+        assert_eq!(AssetsBridge::native_allowances(AccountId32::from(ALICE), spender1), 7);
+    })
+}
+
+#[test]
+fn register_and_deploy_should_work() {
+    new_test_ext().execute_with(|| {
+        // returns the runtime code of the mock erc20
+        let runtime_code = erc20_code(0, 10);
+        let mut code = hex!("6031600c60003960316000f3").to_vec();
+        code.extend_from_slice(&runtime_code);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+
+        assert_noop!(
+            AssetsBridge::register_and_deploy(RuntimeOrigin::signed(BOB.into()), 1),
+            Error::<Test>::RequireAdmin
+        );
+        assert_noop!(
+            AssetsBridge::register_and_deploy(RuntimeOrigin::signed(ALICE.into()), 1),
+            Error::<Test>::Erc20CodeNotSet
+        );
+
+        assert_noop!(
+            AssetsBridge::set_erc20_code(RuntimeOrigin::signed(ALICE.into()), code.clone()),
+            Error::<Test>::RequireAdmin
+        );
+        assert_noop!(
+            AssetsBridge::set_erc20_code(
+                RuntimeOrigin::root(),
+                vec![0u8; MaxErc20CodeSize::get() as usize + 1]
+            ),
+            Error::<Test>::Erc20CodeTooLarge
+        );
+        assert_ok!(AssetsBridge::set_erc20_code(RuntimeOrigin::root(), code.clone()));
+        assert_eq!(AssetsBridge::erc20_code().into_inner(), code);
+
+        assert_noop!(
+            AssetsBridge::register_and_deploy(RuntimeOrigin::signed(ALICE.into()), 1),
+            Error::<Test>::AssetMetadataNotSet
+        );
+
+        assert_ok!(Assets::force_set_metadata(
+            RuntimeOrigin::root(),
+            1,
+            b"Polkadot".to_vec(),
+            b"DOT".to_vec(),
+            10,
+            false
+        ));
+        assert_ok!(AssetsBridge::register_and_deploy(RuntimeOrigin::signed(ALICE.into()), 1));
+
+        let erc20 = AssetsBridge::erc20s(1).unwrap();
+        expect_event(AssetsBridgeEvent::Register(1, erc20));
+        assert_eq!(AssetsBridge::asset_ids(erc20), Some(1));
+        assert_eq!(pallet_evm::AccountCodes::<Test>::get(erc20), runtime_code);
+        assert_eq!(AssetsBridge::net_bridged(1), 0);
+
+        assert_noop!(
+            AssetsBridge::register_and_deploy(RuntimeOrigin::signed(ALICE.into()), 1),
+            Error::<Test>::AssetIdHasMapped
+        );
+    })
+}
+
+#[test]
+fn register_with_decimals_should_scale_amount() {
+    new_test_ext().execute_with(|| {
+        let evm_address = H160::from_slice(&EVM_ADDR);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 2, ALICE.into(), true, 1));
+        assert_ok!(Assets::force_set_metadata(
+            RuntimeOrigin::root(),
+            1,
+            b"Polkadot".to_vec(),
+            b"DOT".to_vec(),
+            10,
+            false
+        ));
+        assert_ok!(Assets::force_set_metadata(
+            RuntimeOrigin::root(),
+            2,
+            b"Ether".to_vec(),
+            b"ETH".to_vec(),
+            18,
+            false
+        ));
+        assert_ok!(Assets::mint(
+            RuntimeOrigin::signed(ALICE.into()),
+            2,
+            ALICE.into(),
+            10u128.pow(18)
+        ));
+
+        pallet_evm::AccountCodes::<Test>::insert(H160::from_slice(&ERC20_1), erc20_code(0, 18));
+        pallet_evm::AccountCodes::<Test>::insert(H160::from_slice(&ERC20_2), erc20_code(0, 6));
+
+        // 1. scale up: 10 decimals asset into 18 decimals erc20
+        assert_noop!(
+            AssetsBridge::register_with_decimals(
+                RuntimeOrigin::signed(ALICE.into()),
+                1,
+                H160::from_slice(&ERC20_1),
+                10
+            ),
+            Error::<Test>::Erc20DecimalsMismatch
+        );
+        assert_ok!(AssetsBridge::register_with_decimals(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1),
+            18
+        ));
+        assert_eq!(AssetsBridge::decimals(1), Some((10, 18)));
+        assert_eq!(AssetsBridge::erc20_amount(1, 15), Ok(15 * 10u128.pow(8)));
+
+        // 2. scale down: 18 decimals asset into 6 decimals erc20
+        assert_ok!(AssetsBridge::register_with_decimals(
+            RuntimeOrigin::signed(ALICE.into()),
+            2,
+            H160::from_slice(&ERC20_2),
+            6
+        ));
+        assert_eq!(AssetsBridge::decimals(2), Some((18, 6)));
+        assert_eq!(AssetsBridge::erc20_amount(2, 3 * 10u128.pow(12)), Ok(3));
+
+        assert_noop!(
+            AssetsBridge::deposit_to(
+                RuntimeOrigin::signed(ALICE.into()),
+                2,
+                10u128.pow(12) + 1,
+                evm_address
+            ),
+            Error::<Test>::DustAmount
+        );
+        assert_ok!(AssetsBridge::deposit_to(
+            RuntimeOrigin::signed(ALICE.into()),
+            2,
+            10u128.pow(12),
+            evm_address
+        ));
+
+        // 3. the same decimals need no scaling
+        assert_ok!(AssetsBridge::force_unregister(RuntimeOrigin::root(), 2));
+        assert_eq!(AssetsBridge::decimals(2), None);
+        pallet_evm::AccountCodes::<Test>::insert(H160::from_slice(&ERC20_2), erc20_code(0, 18));
+        assert_ok!(AssetsBridge::register_with_decimals(
+            RuntimeOrigin::signed(ALICE.into()),
+            2,
+            H160::from_slice(&ERC20_2),
+            18
+        ));
+        assert_eq!(AssetsBridge::decimals(2), None);
+        assert_eq!(AssetsBridge::erc20_amount(2, 10u128.pow(12) + 1), Ok(10u128.pow(12) + 1));
+    })
+}
+
+#[test]
+fn claim_account_eip712_should_work() {
+    use codec::Encode;
+    use sp_io::hashing::keccak_256;
+
+    new_test_ext().execute_with(|| {
+        let domain_separator = keccak_256(&ethabi::encode(&[
+            Token::FixedBytes(
+                keccak_256(
+                    b"EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)",
+                )
+                .to_vec(),
+            ),
+            Token::FixedBytes(keccak_256(b"AssetsBridge").to_vec()),
+            Token::FixedBytes(keccak_256(b"1").to_vec()),
+            Token::Uint(0.into()),
+            Token::FixedBytes(System::block_hash(0).as_bytes().to_vec()),
+        ]));
+        assert_eq!(AssetsBridge::eip712_domain_separator(), domain_separator);
+
+        let bob = AccountId32::from(BOB);
+        let struct_hash = keccak_256(&ethabi::encode(&[
+            Token::FixedBytes(keccak_256(b"Claim(bytes substrateAccount)").to_vec()),
+            Token::FixedBytes(keccak_256(&bob.encode()).to_vec()),
+        ]));
+
+        let mut what = b"\x19\x01".to_vec();
+        what.extend_from_slice(&domain_separator[..]);
+        what.extend_from_slice(&struct_hash[..]);
+
+        let secret = libsecp256k1::SecretKey::parse(&keccak_256(b"bob")).unwrap();
+        let public = libsecp256k1::PublicKey::from_secret_key(&secret);
+        let eth_address = H160::from_slice(&keccak_256(&public.serialize()[1..65])[12..]);
+        let (sig, recovery_id) =
+            libsecp256k1::sign(&libsecp256k1::Message::parse(&keccak_256(&what)), &secret);
+        let mut signature = [0u8; 65];
+        signature[0..64].copy_from_slice(&sig.serialize()[..]);
+        signature[64] = recovery_id.serialize();
+        let signature = EcdsaSignature::from_raw(signature);
+
+        // 1. a personal_sign signature or a signature for another account is invalid
+        assert_noop!(
+            AssetsBridge::claim_account_eip712(
+                RuntimeOrigin::signed(ALICE.into()),
+                H160::from_slice(&EVM_ADDR),
+                EcdsaSignature::from_slice(&SIGNATURE).unwrap()
+            ),
+            Error::<Test>::InvalidSignature
+        );
+        assert_noop!(
+            AssetsBridge::claim_account_eip712(
+                RuntimeOrigin::signed(ALICE.into()),
+                eth_address,
+                signature.clone()
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        // 2. claim
+        assert_ok!(AssetsBridge::claim_account_eip712(
+            RuntimeOrigin::signed(BOB.into()),
+            eth_address,
+            signature
+        ));
+        expect_event(AssetsBridgeEvent::ClaimAccount(bob.clone(), eth_address));
+        assert_eq!(AssetsBridge::evm_accounts(&bob), Some(eth_address));
+        assert_eq!(AssetsBridge::sub_accounts(eth_address), Some(bob));
+    })
+}
+
+#[test]
+fn claim_account_should_move_unclaimed_balance() {
+    new_test_ext().execute_with(|| {
+        let address = H160::from_slice(&EVM_ADDR);
+        let sub_account = AccountId32::from_str(SUB_ACCOUNT).unwrap();
+        let unclaimed = AccountId32::from_str(MAPPING_ACCOUNT).unwrap();
+        let _ = Balances::deposit_creating(&sub_account, 100);
+        let _ = Balances::deposit_creating(&unclaimed, 50);
+
+        // 1. the unclaimed address is mapped by hashing
+        assert_eq!(ClaimedAddressMapping::<Test>::into_account_id(address), unclaimed);
+
+        // 2. claim, the unclaimed balance is moved into the claimed account
+        assert_ok!(AssetsBridge::claim_account(
+            RuntimeOrigin::signed(sub_account.clone()),
+            address,
+            EcdsaSignature::from_slice(&SIGNATURE).unwrap()
+        ));
+        expect_event(AssetsBridgeEvent::ClaimAccount(sub_account.clone(), address));
+        assert_eq!(ClaimedAddressMapping::<Test>::into_account_id(address), sub_account);
+        assert_eq!(Balances::free_balance(&unclaimed), 0);
+        assert_eq!(Balances::free_balance(&sub_account), 100 - ClaimBond::get() + 50);
+
+        // 3. teleport between the claimed account and its evm address is a no-op
+        assert_ok!(AssetsBridge::teleport(
+            RuntimeOrigin::signed(sub_account.clone()),
+            10,
+            ActionType::FromSubToEth
+        ));
+        assert_eq!(Balances::free_balance(&sub_account), 100 - ClaimBond::get() + 50);
+
+        // 4. dissolve, the address is mapped by hashing again
+        assert_ok!(AssetsBridge::dissolve(RuntimeOrigin::signed(sub_account)));
+        assert_eq!(ClaimedAddressMapping::<Test>::into_account_id(address), unclaimed);
+    })
+}
+
+#[test]
+fn claimed_address_should_keep_its_nonce() {
+    new_test_ext().execute_with(|| {
+        let address = H160::from_slice(&EVM_ADDR);
+        let sub_account = AccountId32::from_str(SUB_ACCOUNT).unwrap();
+        let unclaimed = AccountId32::from_str(MAPPING_ACCOUNT).unwrap();
+        let _ = Balances::deposit_creating(&sub_account, 100);
+        let _ = Balances::deposit_creating(&unclaimed, 50);
+        frame_system::Account::<Test>::mutate(&unclaimed, |account| account.nonce = 5);
+
+        // 1. claim, the nonce of the address is kept although its account is reaped
+        assert_ok!(AssetsBridge::claim_account(
+            RuntimeOrigin::signed(sub_account.clone()),
+            address,
+            EcdsaSignature::from_slice(&SIGNATURE).unwrap()
+        ));
+        assert!(!System::account_exists(&unclaimed));
+        assert_eq!(System::account_nonce(&sub_account), 5);
+
+        // 2. dissolve, the nonce of the address is kept
+        frame_system::Account::<Test>::mutate(&sub_account, |account| account.nonce = 8);
+        assert_ok!(AssetsBridge::dissolve(RuntimeOrigin::signed(sub_account.clone())));
+        assert_eq!(System::account_nonce(&unclaimed), 8);
+
+        // 3. the higher nonce is not lowered
+        frame_system::Account::<Test>::mutate(&unclaimed, |account| account.nonce = 3);
+        assert_ok!(AssetsBridge::claim_account(
+            RuntimeOrigin::signed(sub_account.clone()),
+            address,
+            EcdsaSignature::from_slice(&SIGNATURE).unwrap()
+        ));
+        assert_eq!(System::account_nonce(&sub_account), 8);
+    })
+}
+
+#[test]
+fn migrate_unclaimed_balances_to_v3_should_work() {
+    new_test_ext().execute_with(|| {
+        let address = H160::from_slice(&EVM_ADDR);
+        let sub_account = AccountId32::from_str(SUB_ACCOUNT).unwrap();
+        let unclaimed = AccountId32::from_str(MAPPING_ACCOUNT).unwrap();
+        let _ = Balances::deposit_creating(&unclaimed, 50);
+        frame_system::Account::<Test>::mutate(&unclaimed, |account| account.nonce = 5);
+        crate::SubAccounts::<Test>::insert(address, &sub_account);
+        crate::EvmAccounts::<Test>::insert(&sub_account, address);
+
+        StorageVersion::new(2).put::<AssetsBridge>();
+        migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 3);
+        assert_eq!(Balances::free_balance(&unclaimed), 0);
+        assert_eq!(Balances::free_balance(&sub_account), 50);
+        assert_eq!(System::account_nonce(&sub_account), 5);
+
+        // the migration runs once
+        let _ = Balances::deposit_creating(&unclaimed, 50);
+        migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+        assert_eq!(Balances::free_balance(&unclaimed), 50);
+    })
+}
+
+#[test]
+fn volume_limit_should_work() {
+    new_test_ext().execute_with(|| {
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let deposit_to = |who: [u8; 32], amount: u128| {
+            AssetsBridge::deposit_to(RuntimeOrigin::signed(who.into()), 1, amount, evm_address)
+        };
+        let reached = |who: Option<AccountId32>| {
+            System::events().iter().any(|record| {
+                record.event ==
+                    AssetsBridgeEvent::VolumeLimitReached(1, Direction::Deposit, who.clone())
+                        .into()
+            })
+        };
+        let limit = VolumeLimit { window: 10, total: Some(100), per_account: Some(60) };
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, ALICE.into(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE.into()), 1, ALICE.into(), 1000));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE.into()), 1, BOB.into(), 1000));
+        assert_ok!(AssetsBridge::register(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+
+        assert_noop!(
+            AssetsBridge::set_volume_limit(
+                RuntimeOrigin::signed(BOB.into()),
+                1,
+                Direction::Deposit,
+                Some(limit.clone())
+            ),
+            Error::<Test>::RequireAdmin
+        );
+        assert_ok!(AssetsBridge::set_volume_limit(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            Direction::Deposit,
+            Some(limit.clone())
+        ));
+        expect_event(AssetsBridgeEvent::SetVolumeLimit(1, Direction::Deposit, Some(limit)));
+
+        // 1. per-account limit
+        assert_ok!(deposit_to(ALICE, 50));
+        assert_noop!(deposit_to(ALICE, 20), Error::<Test>::VolumeLimitExceeded);
+        assert_ok!(deposit_to(ALICE, 10));
+        assert!(reached(Some(ALICE.into())));
+        assert_eq!(
+            AssetsBridge::account_volumes((1, Direction::Deposit, AccountId32::from(ALICE))),
+            (1, 60)
+        );
+
+        // 2. total limit
+        assert_ok!(deposit_to(BOB, 40));
+        assert!(reached(None));
+        assert_noop!(deposit_to(BOB, 1), Error::<Test>::VolumeLimitExceeded);
+        assert_eq!(AssetsBridge::total_volumes(1, Direction::Deposit), (1, 100));
+
+        // 3. other directions are not limited
+        assert_noop!(
+            AssetsBridge::withdraw(RuntimeOrigin::signed(BOB.into()), 1, 1),
+            Error::<Test>::EthAddressHasNotMapped
+        );
+
+        // 4. next window
+        System::set_block_number(11);
+        assert_ok!(deposit_to(BOB, 40));
+        assert_eq!(AssetsBridge::total_volumes(1, Direction::Deposit), (11, 40));
+
+        // 5. remove the limit
+        assert_ok!(AssetsBridge::set_volume_limit(
+            RuntimeOrigin::signed(ALICE.into()),
+            1,
+            Direction::Deposit,
+            None
+        ));
+        assert_ok!(deposit_to(ALICE, 100));
+    })
+}
+
+#[test]
+fn evm_call_errors_should_be_distinct() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(alice.clone()), 1, erc20));
+        crate::SubAccounts::<Test>::insert(evm_address, &alice);
+        crate::EvmAccounts::<Test>::insert(&alice, evm_address);
+
+        let set_code = |code: &[u8]| pallet_evm::AccountCodes::<Test>::insert(erc20, code.to_vec());
+        let withdraw = || AssetsBridge::withdraw(RuntimeOrigin::signed(alice.clone()), 1, 10);
+
+        // PUSH1 0 PUSH1 0 REVERT
+        set_code(&hex!("60006000fd"));
+        assert_noop!(withdraw(), Error::<Test>::EvmReverted);
+        // JUMPDEST PUSH1 0 JUMP
+        set_code(&hex!("5b600056"));
+        assert_noop!(withdraw(), Error::<Test>::EvmOutOfGas);
+        // INVALID
+        set_code(&hex!("fe"));
+        assert_noop!(withdraw(), Error::<Test>::ExecutedFailed);
+        // return false: PUSH1 32 PUSH1 0 RETURN
+        set_code(&hex!("60206000f3"));
+        assert_noop!(withdraw(), Error::<Test>::InvalidReturnData);
+        // return true: PUSH1 1 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        set_code(&hex!("600160005260206000f3"));
+        assert_ok!(withdraw());
+        assert_eq!(Assets::balance(1, &alice), 10);
+    })
+}
+
+#[test]
+fn set_gas_limit_should_work() {
+    use crate::{WeightInfo, GAS_LIMIT, MAX_GAS_LIMIT};
+
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        crate::SubAccounts::<Test>::insert(evm_address, &alice);
+        crate::EvmAccounts::<Test>::insert(&alice, evm_address);
+
+        assert_noop!(
+            AssetsBridge::set_gas_limit(RuntimeOrigin::signed(alice.clone()), 1, Some(100_000)),
+            Error::<Test>::AssetIdHasNotMapped
+        );
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(alice.clone()), 1, erc20));
+        assert_eq!(AssetsBridge::gas_limit(1), GAS_LIMIT);
+
+        assert_noop!(
+            AssetsBridge::set_gas_limit(RuntimeOrigin::signed(BOB.into()), 1, Some(100_000)),
+            Error::<Test>::RequireAdmin
+        );
+        assert_noop!(
+            AssetsBridge::set_gas_limit(RuntimeOrigin::signed(alice.clone()), 1, Some(0)),
+            Error::<Test>::InvalidGasLimit
+        );
+        assert_noop!(
+            AssetsBridge::set_gas_limit(
+                RuntimeOrigin::signed(alice.clone()),
+                1,
+                Some(MAX_GAS_LIMIT + 1)
+            ),
+            Error::<Test>::InvalidGasLimit
+        );
+
+        assert_ok!(AssetsBridge::set_gas_limit(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            Some(100_000)
+        ));
+        expect_event(AssetsBridgeEvent::SetGasLimit(1, Some(100_000)));
+        assert_eq!(AssetsBridge::gas_limit(1), 100_000);
+        assert_eq!(
+            AssetsBridge::evm_call_weight(1),
+            AssetsBridge::gas_weight(100_000)
+                .saturating_add(<Test as frame_system::Config>::DbWeight::get().reads(1))
+        );
+
+        // out of the gas limit: JUMPDEST PUSH1 0 JUMP
+        pallet_evm::AccountCodes::<Test>::insert(erc20, hex!("5b600056").to_vec());
+        assert_noop!(
+            AssetsBridge::withdraw(RuntimeOrigin::signed(alice.clone()), 1, 10),
+            Error::<Test>::EvmOutOfGas
+        );
+
+        // only the used gas is charged: PUSH1 1 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        pallet_evm::AccountCodes::<Test>::insert(erc20, hex!("600160005260206000f3").to_vec());
+        let post_info =
+            AssetsBridge::withdraw(RuntimeOrigin::signed(alice.clone()), 1, 10).unwrap();
+        let actual_weight = post_info.actual_weight.unwrap();
+        assert!(actual_weight.ref_time() > <() as WeightInfo>::withdraw().ref_time());
+        assert!(
+            actual_weight.ref_time() <
+                <() as WeightInfo>::withdraw()
+                    .saturating_add(AssetsBridge::evm_call_weight(1))
+                    .ref_time()
+        );
+
+        // reset to the default gas limit
+        assert_ok!(AssetsBridge::set_gas_limit(RuntimeOrigin::signed(alice.clone()), 1, None));
+        expect_event(AssetsBridgeEvent::SetGasLimit(1, None));
+        assert_eq!(AssetsBridge::gas_limit(1), GAS_LIMIT);
+
+        // unregister removes the gas limit
+        assert_ok!(AssetsBridge::set_gas_limit(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            Some(100_000)
+        ));
+        assert_ok!(AssetsBridge::force_unregister(RuntimeOrigin::root(), 1));
+        assert_eq!(AssetsBridge::gas_limits(1), None);
+    })
+}
+
+#[test]
+fn back_foreign_queue_should_work() {
+    use codec::Encode;
+
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let bob = AccountId32::from(BOB);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(alice.clone()), 1, bob.clone(), 100));
+        assert_ok!(AssetsBridge::register(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert_ok!(AssetsBridge::back_foreign(RuntimeOrigin::signed(alice.clone()), 1, false));
+
+        // 1. set relayer
+        assert_noop!(
+            AssetsBridge::set_relayer(RuntimeOrigin::signed(bob.clone()), alice.clone()),
+            Error::<Test>::RequireAdmin
+        );
+        assert_ok!(AssetsBridge::set_relayer(RuntimeOrigin::signed(alice.clone()), alice.clone()));
+        expect_event(AssetsBridgeEvent::SetRelayer(alice.clone()));
+
+        // 2. the back foreign transfers are queued by nonce
+        for _ in 0..3 {
+            assert_ok!(AssetsBridge::teleport(
+                RuntimeOrigin::signed(bob.clone()),
+                10,
+                ActionType::BackForeign(1)
+            ));
+        }
+        assert!(System::events().iter().any(|r| r.event ==
+            AssetsBridgeEvent::BackForeignQueued(
+                2,
+                bob.clone(),
+                1,
+                10,
+                bob.encode().try_into().unwrap()
+            )
+            .into()));
+        assert_eq!(AssetsBridge::back_foreign_nonce(), 3);
+        assert_eq!(Assets::balance(1, &bob), 70);
+
+        let transfer = AssetsBridge::pending_back_foreign(0).unwrap();
+        assert_eq!(transfer.who, bob);
+        assert_eq!(transfer.amount, 10);
+        assert_eq!(transfer.dest.to_vec(), bob.encode());
+        assert_eq!(transfer.deadline, 1 + BackForeignTimeout::get());
+        assert_eq!(transfer.status, BackForeignStatus::Pending);
+
+        // 3. confirm by the relayer
+        assert_noop!(
+            AssetsBridge::confirm_back_foreign(RuntimeOrigin::signed(bob.clone()), 0),
+            Error::<Test>::RequireRelayer
+        );
+        assert_noop!(
+            AssetsBridge::confirm_back_foreign(RuntimeOrigin::signed(alice.clone()), 3),
+            Error::<Test>::BackForeignNotFound
+        );
+        assert_ok!(AssetsBridge::confirm_back_foreign(RuntimeOrigin::signed(alice.clone()), 0));
+        expect_event(AssetsBridgeEvent::BackForeignConfirmed(0));
+        assert_eq!(
+            AssetsBridge::pending_back_foreign(0).unwrap().status,
+            BackForeignStatus::Confirmed
+        );
+        assert_noop!(
+            AssetsBridge::refund_back_foreign(RuntimeOrigin::signed(alice.clone()), 0),
+            Error::<Test>::BackForeignNotPending
+        );
+
+        // 4. refund by the relayer
+        assert_ok!(AssetsBridge::refund_back_foreign(RuntimeOrigin::signed(alice.clone()), 1));
+        expect_event(AssetsBridgeEvent::BackForeignRefunded(1));
+        assert_eq!(Assets::balance(1, &bob), 80);
+        assert_eq!(
+            AssetsBridge::pending_back_foreign(1).unwrap().status,
+            BackForeignStatus::Refunded
+        );
+
+        // 5. reclaim by the owner after the deadline
+        assert_noop!(
+            AssetsBridge::reclaim_back_foreign(RuntimeOrigin::signed(bob.clone()), 2),
+            Error::<Test>::BackForeignNotExpired
+        );
+        System::set_block_number(1 + BackForeignTimeout::get());
+        assert_noop!(
+            AssetsBridge::reclaim_back_foreign(RuntimeOrigin::signed(alice.clone()), 2),
+            Error::<Test>::BackForeignNotFound
+        );
+        assert_ok!(AssetsBridge::reclaim_back_foreign(RuntimeOrigin::signed(bob.clone()), 2));
+        expect_event(AssetsBridgeEvent::BackForeignReclaimed(2));
+        assert_eq!(Assets::balance(1, &bob), 90);
+        assert_eq!(
+            AssetsBridge::pending_back_foreign(2).unwrap().status,
+            BackForeignStatus::Reclaimed
+        );
+        assert_noop!(
+            AssetsBridge::confirm_back_foreign(RuntimeOrigin::signed(alice.clone()), 2),
+            Error::<Test>::BackForeignNotPending
+        );
+
+        // 6. the finished transfers are removed after the retention
+        let (finished_at, reclaimed_at) = (1, 1 + BackForeignTimeout::get());
+        assert_eq!(
+            AssetsBridge::back_foreign_expiries(finished_at + BackForeignRetention::get() + 1)
+                .to_vec(),
+            vec![0, 1]
+        );
+        AssetsBridge::on_initialize(finished_at + BackForeignRetention::get() + 1);
+        assert!(AssetsBridge::pending_back_foreign(0).is_none());
+        assert!(AssetsBridge::pending_back_foreign(1).is_none());
+        assert!(AssetsBridge::pending_back_foreign(2).is_some());
+        AssetsBridge::on_initialize(reclaimed_at + BackForeignRetention::get() + 1);
+        assert!(AssetsBridge::pending_back_foreign(2).is_none());
+        assert_eq!(crate::BackForeignExpiries::<Test>::iter().count(), 0);
+
+        // 7. the transfers finished in one block are bounded
+        assert_ok!(AssetsBridge::teleport(
+            RuntimeOrigin::signed(bob.clone()),
+            10,
+            ActionType::BackForeign(1)
+        ));
+        crate::BackForeignExpiries::<Test>::insert(
+            reclaimed_at + BackForeignRetention::get() + 1,
+            BoundedVec::try_from(vec![0; crate::MAX_FINISHED_BACK_FOREIGN as usize]).unwrap(),
+        );
+        assert_noop!(
+            AssetsBridge::confirm_back_foreign(RuntimeOrigin::signed(alice.clone()), 3),
+            Error::<Test>::TooManyFinishedBackForeign
+        );
+    })
+}
+
+#[test]
+fn back_foreign_cancel_should_undo_volume() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let bob = AccountId32::from(BOB);
+        let teleport = || {
+            AssetsBridge::teleport(
+                RuntimeOrigin::signed(bob.clone()),
+                10,
+                ActionType::BackForeign(1),
+            )
+        };
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(alice.clone()), 1, bob.clone(), 100));
+        assert_ok!(AssetsBridge::register(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert_ok!(AssetsBridge::back_foreign(RuntimeOrigin::signed(alice.clone()), 1, false));
+        assert_ok!(AssetsBridge::set_relayer(RuntimeOrigin::signed(alice.clone()), alice.clone()));
+        let window = BackForeignTimeout::get() * 2;
+        assert_ok!(AssetsBridge::set_volume_limit(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            Direction::BackForeign,
+            Some(VolumeLimit { window, total: Some(30), per_account: Some(20) })
+        ));
+
+        // 1. the refunded amount is removed from the volumes
+        assert_ok!(teleport());
+        assert_ok!(teleport());
+        assert_noop!(teleport(), Error::<Test>::VolumeLimitExceeded);
+        assert_ok!(AssetsBridge::refund_back_foreign(RuntimeOrigin::signed(alice.clone()), 0));
+        assert_eq!(AssetsBridge::total_volumes(1, Direction::BackForeign), (1, 10));
+        assert_eq!(AssetsBridge::account_volumes((1, Direction::BackForeign, &bob)), (1, 10));
+        assert_ok!(teleport());
+
+        // 2. the reclaimed amount is removed from the volumes
+        System::set_block_number(1 + BackForeignTimeout::get());
+        assert_ok!(AssetsBridge::reclaim_back_foreign(RuntimeOrigin::signed(bob.clone()), 1));
+        assert_eq!(AssetsBridge::total_volumes(1, Direction::BackForeign), (1, 10));
+        assert_eq!(AssetsBridge::account_volumes((1, Direction::BackForeign, &bob)), (1, 10));
+        assert_eq!(Assets::balance(1, &bob), 90);
+
+        // 3. the amount noted in a restarted window is not removed
+        System::set_block_number(1 + window);
+        assert_ok!(teleport());
+        System::set_block_number(1 + window + BackForeignTimeout::get());
+        assert_ok!(AssetsBridge::reclaim_back_foreign(RuntimeOrigin::signed(bob.clone()), 2));
+        assert_eq!(AssetsBridge::total_volumes(1, Direction::BackForeign), (1 + window, 10));
+        assert_ok!(AssetsBridge::refund_back_foreign(RuntimeOrigin::signed(alice.clone()), 3));
+        assert_eq!(AssetsBridge::total_volumes(1, Direction::BackForeign), (1 + window, 0));
+    })
+}
+
+#[test]
+fn back_foreign_xcm_should_work() {
+    use xcm::{
+        latest::{
+            Instruction::{BuyExecution, ClearOrigin, DepositAsset, WithdrawAsset},
+            Junction::{self, GeneralIndex, PalletInstance, Parachain},
+            Junctions::{X1, X2, X3},
+            MultiAsset,
+            MultiAssetFilter::Wild,
+            MultiLocation, NetworkId,
+            WeightLimit::Unlimited,
+            WildMultiAsset::All,
+            Xcm,
+        },
+        VersionedMultiLocation,
+    };
+
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let bob = AccountId32::from(BOB);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(alice.clone()), 1, bob.clone(), 100));
+        assert_ok!(AssetsBridge::register(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+        assert_ok!(AssetsBridge::back_foreign(RuntimeOrigin::signed(alice.clone()), 1, false));
+
+        // 1. set the reserve location
+        let location =
+            MultiLocation::new(1, X3(Parachain(1000), PalletInstance(50), GeneralIndex(8)));
+        let versioned = || Some(Box::new(VersionedMultiLocation::from(location.clone())));
+        assert_noop!(
+            AssetsBridge::set_reserve_location(RuntimeOrigin::signed(bob.clone()), 1, versioned()),
+            Error::<Test>::RequireAdmin
+        );
+        assert_ok!(AssetsBridge::set_reserve_location(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            versioned()
+        ));
+        expect_event(AssetsBridgeEvent::SetReserveLocation(1, Some(location.clone())));
+        assert_eq!(AssetsBridge::reserve_locations(1), Some(location));
+
+        // 2. back foreign by xcm instead of the relayer
+        assert_ok!(AssetsBridge::teleport(
+            RuntimeOrigin::signed(bob.clone()),
+            10,
+            ActionType::BackForeign(1)
+        ));
+        let reserve = MultiLocation::new(1, X1(Parachain(1000)));
+        let beneficiary: MultiLocation =
+            X1(Junction::AccountId32 { network: NetworkId::Any, id: BOB }).into();
+        assert!(System::events().iter().any(|r| r.event ==
+            AssetsBridgeEvent::BackForeignXcmSent(
+                bob.clone(),
+                1,
+                10,
+                reserve.clone(),
+                beneficiary.clone()
+            )
+            .into()));
+        assert_eq!(Assets::balance(1, &bob), 90);
+        assert_eq!(AssetsBridge::back_foreign_nonce(), 0);
+
+        let asset: MultiAsset =
+            (MultiLocation::new(0, X2(PalletInstance(50), GeneralIndex(8))), 10u128).into();
+        assert_eq!(
+            sent_xcm(),
+            vec![(
+                reserve,
+                Xcm(vec![
+                    WithdrawAsset(asset.clone().into()),
+                    ClearOrigin,
+                    BuyExecution { fees: asset, weight_limit: Unlimited },
+                    DepositAsset { assets: Wild(All), max_assets: 1, beneficiary },
+                ])
+            )]
+        );
+
+        // 3. the relay chain asset
+        assert_ok!(AssetsBridge::set_reserve_location(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            Some(Box::new(MultiLocation::parent().into()))
+        ));
+        assert_ok!(AssetsBridge::teleport(
+            RuntimeOrigin::signed(bob.clone()),
+            10,
+            ActionType::BackForeign(1)
+        ));
+        assert_eq!(sent_xcm().last().unwrap().0, MultiLocation::parent());
+
+        // 4. the unreachable reserve chain
+        assert_ok!(AssetsBridge::set_reserve_location(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            Some(Box::new(MultiLocation::new(1, X2(Parachain(2000), GeneralIndex(8))).into()))
+        ));
+        assert_noop!(
+            AssetsBridge::teleport(
+                RuntimeOrigin::signed(bob.clone()),
+                10,
+                ActionType::BackForeign(1)
+            ),
+            Error::<Test>::XcmSendFailed
+        );
+
+        // 5. back to the relayer
+        assert_ok!(AssetsBridge::set_reserve_location(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            None
+        ));
+        expect_event(AssetsBridgeEvent::SetReserveLocation(1, None));
+        assert_ok!(AssetsBridge::teleport(
+            RuntimeOrigin::signed(bob.clone()),
+            10,
+            ActionType::BackForeign(1)
+        ));
+        assert_eq!(AssetsBridge::back_foreign_nonce(), 1);
+        assert_eq!(sent_xcm().len(), 2);
+    })
+}
+
+#[test]
+fn back_foreign_dest_should_work() {
+    use xcm::latest::{Junction, Junctions::X1, MultiLocation, NetworkId, Parent};
+
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let bob = AccountId32::from(BOB);
+        let key = [7u8; 20];
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(alice.clone()), 1, bob.clone(), 100));
+        assert_ok!(AssetsBridge::register(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            H160::from_slice(&ERC20_1)
+        ));
+
+        // 1. ensure the asset in the back foreign list and the amount not zero
+        assert_noop!(
+            AssetsBridge::transfer_back_foreign(
+                RuntimeOrigin::signed(bob.clone()),
+                1,
+                10,
+                ALICE.to_vec().try_into().unwrap()
+            ),
+            Error::<Test>::BanBackForeign
+        );
+        assert_ok!(AssetsBridge::back_foreign(RuntimeOrigin::signed(alice.clone()), 1, false));
+        assert_noop!(
+            AssetsBridge::transfer_back_foreign(
+                RuntimeOrigin::signed(bob.clone()),
+                1,
+                0,
+                ALICE.to_vec().try_into().unwrap()
+            ),
+            Error::<Test>::ZeroBalance
+        );
+
+        // 2. the AccountId32 destinations by default
+        assert_noop!(
+            AssetsBridge::transfer_back_foreign(
+                RuntimeOrigin::signed(bob.clone()),
+                1,
+                10,
+                key.to_vec().try_into().unwrap()
+            ),
+            Error::<Test>::InvalidDest
+        );
+        assert_ok!(AssetsBridge::transfer_back_foreign(
+            RuntimeOrigin::signed(bob.clone()),
+            1,
+            10,
+            ALICE.to_vec().try_into().unwrap()
+        ));
+        expect_event(AssetsBridgeEvent::BackForeignQueued(
+            0,
+            bob.clone(),
+            1,
+            10,
+            ALICE.to_vec().try_into().unwrap(),
+        ));
+        assert_eq!(Assets::balance(1, &bob), 90);
+
+        // 3. the AccountKey20 destinations
+        assert_noop!(
+            AssetsBridge::set_dest_format(
+                RuntimeOrigin::signed(bob.clone()),
+                1,
+                DestFormat::AccountKey20
+            ),
+            Error::<Test>::RequireAdmin
+        );
+        assert_ok!(AssetsBridge::set_dest_format(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            DestFormat::AccountKey20
+        ));
+        expect_event(AssetsBridgeEvent::SetDestFormat(1, DestFormat::AccountKey20));
+        assert_noop!(
+            AssetsBridge::transfer_back_foreign(
+                RuntimeOrigin::signed(bob.clone()),
+                1,
+                10,
+                ALICE.to_vec().try_into().unwrap()
+            ),
+            Error::<Test>::InvalidDest
+        );
+        // the caller is not a valid destination
+        assert_noop!(
+            AssetsBridge::teleport(
+                RuntimeOrigin::signed(bob.clone()),
+                10,
+                ActionType::BackForeign(1)
+            ),
+            Error::<Test>::InvalidDest
+        );
+        assert_ok!(AssetsBridge::set_reserve_location(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            Some(Box::new(MultiLocation::parent().into()))
+        ));
+        assert_ok!(AssetsBridge::transfer_back_foreign(
+            RuntimeOrigin::signed(bob.clone()),
+            1,
+            10,
+            key.to_vec().try_into().unwrap()
+        ));
+        expect_event(AssetsBridgeEvent::BackForeignXcmSent(
+            bob.clone(),
+            1,
+            10,
+            Parent.into(),
+            X1(Junction::AccountKey20 { network: NetworkId::Any, key }).into(),
+        ));
+        assert_eq!(Assets::balance(1, &bob), 80);
+
+        // 4. the Raw destinations can't be sent by xcm
+        assert_ok!(AssetsBridge::set_dest_format(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            DestFormat::Raw
+        ));
+        assert_noop!(
+            AssetsBridge::teleport(
+                RuntimeOrigin::signed(bob.clone()),
+                10,
+                ActionType::BackForeign(1)
+            ),
+            Error::<Test>::InvalidDest
+        );
+        assert_noop!(
+            AssetsBridge::transfer_back_foreign(
+                RuntimeOrigin::signed(bob.clone()),
+                1,
+                10,
+                b"bc1q".to_vec().try_into().unwrap()
+            ),
+            Error::<Test>::InvalidDest
+        );
+        assert_ok!(AssetsBridge::set_reserve_location(
+            RuntimeOrigin::signed(alice.clone()),
+            1,
+            None
+        ));
+        assert_noop!(
+            AssetsBridge::transfer_back_foreign(
+                RuntimeOrigin::signed(bob.clone()),
+                1,
+                10,
+                Vec::<u8>::new().try_into().unwrap()
+            ),
+            Error::<Test>::InvalidDest
+        );
+        assert_ok!(AssetsBridge::transfer_back_foreign(
+            RuntimeOrigin::signed(bob.clone()),
+            1,
+            10,
+            b"bc1q".to_vec().try_into().unwrap()
+        ));
+        expect_event(AssetsBridgeEvent::BackForeignQueued(
+            1,
+            bob.clone(),
+            1,
+            10,
+            b"bc1q".to_vec().try_into().unwrap(),
+        ));
+        assert_eq!(Assets::balance(1, &bob), 70);
+    })
+}
+
+#[test]
+fn net_bridged_should_work() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(alice.clone()), 1, alice.clone(), 100));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(alice.clone()), 1, erc20));
+        crate::SubAccounts::<Test>::insert(evm_address, &alice);
+        crate::EvmAccounts::<Test>::insert(&alice, evm_address);
+
+        // 1. updated on every deposit and withdraw
+        assert_ok!(AssetsBridge::deposit(RuntimeOrigin::signed(alice.clone()), 1, 10));
+        assert_eq!(AssetsBridge::net_bridged(1), 10);
+        assert_ok!(AssetsBridge::withdraw(RuntimeOrigin::signed(alice.clone()), 1, 4));
+        assert_eq!(AssetsBridge::net_bridged(1), 6);
+
+        // 2. equals the total supply: PUSH1 6 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        pallet_evm::AccountCodes::<Test>::insert(erc20, hex!("600660005260206000f3").to_vec());
+        assert_eq!(AssetsBridge::net_bridged_drift(1), Ok(None));
+        assert_noop!(AssetsBridge::report_drift(RuntimeOrigin::none(), 1), Error::<Test>::NoDrift);
+        assert_eq!(
+            AssetsBridge::validate_unsigned(
+                TransactionSource::External,
+                &crate::Call::report_drift { asset_id: 1 }
+            ),
+            Err(InvalidTransaction::Call.into())
+        );
+
+        // 3. drifts from the total supply: PUSH1 7 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        pallet_evm::AccountCodes::<Test>::insert(erc20, hex!("600760005260206000f3").to_vec());
+        assert_eq!(AssetsBridge::net_bridged_drift(1), Ok(Some((6, U256::from(7)))));
+        assert!(AssetsBridge::validate_unsigned(
+            TransactionSource::External,
+            &crate::Call::report_drift { asset_id: 1 }
+        )
+        .is_ok());
+        assert_noop!(
+            AssetsBridge::report_drift(RuntimeOrigin::signed(alice.clone()), 1),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(AssetsBridge::report_drift(RuntimeOrigin::none(), 1));
+        assert!(System::events()
+            .iter()
+            .any(|r| r.event == AssetsBridgeEvent::NetBridgedDrift(1, 6, U256::from(7)).into()));
+        expect_event(AssetsBridgeEvent::Paused(1, None));
+        assert_eq!(AssetsBridge::emergencies(1), PauseFlags::all());
+        assert_eq!(
+            AssetsBridge::validate_unsigned(
+                TransactionSource::External,
+                &crate::Call::report_drift { asset_id: 1 }
+            ),
+            Err(InvalidTransaction::Stale.into())
+        );
+
+        // 4. the erc20 burned outside of the bridge is no drift
+        pallet_evm::AccountCodes::<Test>::insert(erc20, hex!("600560005260206000f3").to_vec());
+        assert_eq!(AssetsBridge::net_bridged_drift(1), Ok(None));
+
+        // 5. removed by force_unregister
+        assert_ok!(AssetsBridge::force_unregister(RuntimeOrigin::root(), 1));
+        assert_eq!(AssetsBridge::net_bridged(1), 0);
+    })
+}
+
+#[test]
+fn register_should_start_net_bridged_from_total_supply() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let evm_address = H160::from_slice(&EVM_ADDR);
+        let erc20 = H160::from_slice(&ERC20_1);
+        let erc20_2 = H160::from_slice(&ERC20_2);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(Assets::mint(RuntimeOrigin::signed(alice.clone()), 1, alice.clone(), 100));
+        crate::SubAccounts::<Test>::insert(evm_address, &alice);
+        crate::EvmAccounts::<Test>::insert(&alice, evm_address);
+
+        // 1. the erc20 minted before the registration
+        pallet_evm::AccountCodes::<Test>::insert(erc20, erc20_code(6, 0));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(alice.clone()), 1, erc20));
+        assert_eq!(AssetsBridge::net_bridged(1), 6);
+        assert_eq!(AssetsBridge::net_bridged_drift(1), Ok(None));
+
+        assert_ok!(AssetsBridge::deposit(RuntimeOrigin::signed(alice.clone()), 1, 10));
+        assert_eq!(AssetsBridge::net_bridged(1), 16);
+
+        // 2. registered again with a different erc20
+        assert_ok!(AssetsBridge::force_unregister(RuntimeOrigin::root(), 1));
+        pallet_evm::AccountCodes::<Test>::insert(erc20_2, erc20_code(3, 0));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(alice.clone()), 1, erc20_2));
+        assert_eq!(AssetsBridge::net_bridged(1), 3);
+        assert_eq!(AssetsBridge::net_bridged_drift(1), Ok(None));
+        assert_eq!(
+            AssetsBridge::validate_unsigned(
+                TransactionSource::External,
+                &crate::Call::report_drift { asset_id: 1 }
+            ),
+            Err(InvalidTransaction::Call.into())
+        );
+
+        // 3. an erc20 without `totalSupply()` can not be registered
+        assert_ok!(AssetsBridge::force_unregister(RuntimeOrigin::root(), 1));
+        pallet_evm::AccountCodes::<Test>::remove(erc20_2);
+        assert_noop!(
+            AssetsBridge::register(RuntimeOrigin::signed(alice.clone()), 1, erc20_2),
+            Error::<Test>::InvalidReturnData
+        );
+    })
+}
+
+#[test]
+fn offchain_worker_should_report_drift() {
+    use codec::Decode;
+    use frame_support::traits::OffchainWorker;
+    use sp_core::offchain::{testing::TestTransactionPoolExt, TransactionPoolExt};
+
+    let mut ext = new_test_ext();
+    let (pool, state) = TestTransactionPoolExt::new();
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(alice.clone()), 1, erc20));
+        // PUSH1 7 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        pallet_evm::AccountCodes::<Test>::insert(erc20, hex!("600760005260206000f3").to_vec());
+
+        // 1. only checks every `DriftCheckInterval` blocks
+        AssetsBridge::offchain_worker(DriftCheckInterval::get() - 1);
+        assert!(state.read().transactions.is_empty());
+
+        // 2. reports the drift by an unsigned transaction
+        AssetsBridge::offchain_worker(DriftCheckInterval::get());
+        let tx = state.write().transactions.pop().unwrap();
+        assert!(state.read().transactions.is_empty());
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature, None);
+        assert_eq!(tx.call, RuntimeCall::AssetsBridge(crate::Call::report_drift { asset_id: 1 }));
+
+        // 3. skips the assets paused in all directions
+        assert_ok!(AssetsBridge::report_drift(RuntimeOrigin::none(), 1));
+        AssetsBridge::offchain_worker(DriftCheckInterval::get() * 2);
+        assert!(state.read().transactions.is_empty());
+    })
+}
+
+#[test]
+fn migrate_net_bridged_to_v4_should_work() {
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let erc20 = H160::from_slice(&ERC20_1);
+        let erc20_2 = H160::from_slice(&ERC20_2);
+
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 2, alice.clone(), true, 1));
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::signed(alice.clone()), 1, erc20));
+        crate::Erc20s::<Test>::insert(2, erc20_2);
+        crate::AssetIds::<Test>::insert(erc20_2, 2);
+        crate::BridgeModes::<Test>::insert(2, crate::BridgeMode::LockRelease);
+        // PUSH1 7 PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        pallet_evm::AccountCodes::<Test>::insert(erc20, hex!("600760005260206000f3").to_vec());
+        pallet_evm::AccountCodes::<Test>::insert(erc20_2, hex!("600760005260206000f3").to_vec());
+
+        StorageVersion::new(3).put::<AssetsBridge>();
+        migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 4);
+        assert_eq!(AssetsBridge::net_bridged(1), 7);
+        assert_eq!(AssetsBridge::net_bridged(2), 0);
+        assert_eq!(AssetsBridge::net_bridged_drift(1), Ok(None));
+    })
+}
+
+#[test]
+fn migrate_erc20s_counter_to_v5_should_work() {
+    new_test_ext().execute_with(|| {
+        migrations::v5::Erc20s::<Test>::insert(1, H160::from_slice(&ERC20_1));
+        migrations::v5::Erc20s::<Test>::insert(2, H160::from_slice(&ERC20_2));
+        assert_eq!(crate::Erc20s::<Test>::count(), 0);
+
+        StorageVersion::new(4).put::<AssetsBridge>();
+        migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+
+        assert_eq!(AssetsBridge::on_chain_storage_version(), 5);
+        assert_eq!(crate::Erc20s::<Test>::count(), 2);
+        assert_eq!(AssetsBridge::assets_to_pause(None), 2);
+    })
+}
+
+#[test]
+fn origins_should_work() {
+    use crate::EnsureAdmin;
+    use frame_support::traits::EnsureOrigin;
+
+    new_test_ext().execute_with(|| {
+        let alice = AccountId32::from(ALICE);
+        let bob = AccountId32::from(BOB);
+        let erc20 = H160::from_slice(&ERC20_1);
+
+        // 1. the admin is ensured by `EnsureAdmin`
+        assert_eq!(
+            EnsureAdmin::<Test>::try_origin(RuntimeOrigin::signed(alice.clone())).ok(),
+            Some(alice.clone())
+        );
+        assert!(EnsureAdmin::<Test>::try_origin(RuntimeOrigin::signed(bob.clone())).is_err());
+        assert!(EnsureAdmin::<Test>::try_origin(RuntimeOrigin::root()).is_err());
+
+        // 2. root or admin by `RegisterOrigin`, `PauseOrigin` and `BackForeignOrigin`
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, alice.clone(), true, 1));
+        assert_noop!(
+            AssetsBridge::register(RuntimeOrigin::signed(bob.clone()), 1, erc20),
+            Error::<Test>::RequireAdmin
+        );
+        assert_ok!(AssetsBridge::register(RuntimeOrigin::root(), 1, erc20));
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::root(), Some(1), None));
+        assert_ok!(AssetsBridge::unpause(RuntimeOrigin::signed(alice.clone()), Some(1), None));
+        assert_ok!(AssetsBridge::back_foreign(RuntimeOrigin::root(), 1, false));
+
+        // 3. only root by `AdminOrigin`
+        assert_noop!(
+            AssetsBridge::set_admin(RuntimeOrigin::signed(alice.clone()), bob.clone()),
+            Error::<Test>::RequireAdmin
+        );
+        assert_noop!(
+            AssetsBridge::force_unregister(RuntimeOrigin::signed(alice.clone()), 1),
+            Error::<Test>::RequireAdmin
+        );
+        assert_ok!(AssetsBridge::set_admin(RuntimeOrigin::root(), bob.clone()));
+        expect_event(AssetsBridgeEvent::SetAdmin(bob.clone()));
+
+        // 4. the new admin takes over
+        assert_noop!(
+            AssetsBridge::pause(RuntimeOrigin::signed(alice), Some(1), None),
+            Error::<Test>::RequireAdmin
+        );
+        assert_ok!(AssetsBridge::pause(RuntimeOrigin::signed(bob), Some(1), None));
+        expect_event(AssetsBridgeEvent::Paused(1, None));
     })
 }
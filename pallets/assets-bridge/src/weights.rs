@@ -0,0 +1,764 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for pallet_assets_bridge
+//!
+//! The EVM execution of `deposit` and `withdraw` is not part of these weights,
+//! it is charged separately from the gas limit of the inner erc20 call.
+//!
+//! The numbers are estimates over the storage accesses of the current code, not generated by
+//! `benchmark pallet`.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_assets_bridge.
+pub trait WeightInfo {
+     fn claim_account() -> Weight;
+     fn dissolve() -> Weight;
+     fn deposit() -> Weight;
+     fn withdraw() -> Weight;
+     fn teleport() -> Weight;
+     fn register() -> Weight;
+     fn pause(n: u32) -> Weight;
+     fn unpause(n: u32) -> Weight;
+     fn back_foreign() -> Weight;
+     fn set_admin() -> Weight;
+     fn force_unregister() -> Weight;
+     fn deposit_to() -> Weight;
+     fn withdraw_to() -> Weight;
+     fn set_erc20_code(c: u32) -> Weight;
+     fn register_and_deploy() -> Weight;
+     fn register_with_decimals() -> Weight;
+     fn claim_account_eip712() -> Weight;
+     fn set_volume_limit() -> Weight;
+     fn withdraw_signed() -> Weight;
+     fn register_lock_release() -> Weight;
+     fn set_gas_limit() -> Weight;
+     fn set_relayer() -> Weight;
+     fn confirm_back_foreign() -> Weight;
+     fn refund_back_foreign() -> Weight;
+     fn reclaim_back_foreign() -> Weight;
+     fn set_reserve_location() -> Weight;
+     fn set_dest_format() -> Weight;
+     fn transfer_back_foreign() -> Weight;
+     fn report_drift() -> Weight;
+}
+
+/// Weights for pallet_assets_bridge using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+     // Storage: AssetsBridge EvmAccounts (r:1 w:1)
+     // Storage: AssetsBridge SubAccounts (r:1 w:1)
+     // Storage: System Account (r:2 w:2)
+     fn claim_account() -> Weight {
+          Weight::from_ref_time(118_402_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(4 as u64))
+               .saturating_add(T::DbWeight::get().writes(4 as u64))
+     }
+     // Storage: AssetsBridge EvmAccounts (r:1 w:1)
+     // Storage: AssetsBridge SubAccounts (r:1 w:1)
+     // Storage: System Account (r:2 w:2)
+     fn dissolve() -> Weight {
+          Weight::from_ref_time(41_130_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(4 as u64))
+               .saturating_add(T::DbWeight::get().writes(4 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge EvmAccounts (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn deposit() -> Weight {
+          Weight::from_ref_time(96_745_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(16 as u64))
+               .saturating_add(T::DbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge EvmAccounts (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn withdraw() -> Weight {
+          Weight::from_ref_time(90_318_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(16 as u64))
+               .saturating_add(T::DbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge EvmAccounts (r:1 w:0)
+     // Storage: System Account (r:2 w:2)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BackForeignNonce (r:1 w:1)
+     // Storage: AssetsBridge PendingBackForeign (r:0 w:1)
+     // Storage: AssetsBridge ReserveLocations (r:1 w:0)
+     fn teleport() -> Weight {
+          Weight::from_ref_time(52_604_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(9 as u64))
+               .saturating_add(T::DbWeight::get().writes(7 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: AssetsBridge NetBridged (r:0 w:1)
+     fn register() -> Weight {
+          Weight::from_ref_time(26_977_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(5 as u64))
+               .saturating_add(T::DbWeight::get().writes(4 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge Emergencies (r:1 w:1)
+     fn pause(n: u32) -> Weight {
+          Weight::from_ref_time(24_518_000 as u64)
+               // Standard Error: 2_000
+               .saturating_add(Weight::from_ref_time(4_187_000 as u64).saturating_mul(n as u64))
+               .saturating_add(T::DbWeight::get().reads(1 as u64))
+               .saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+               .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge Emergencies (r:1 w:1)
+     fn unpause(n: u32) -> Weight {
+          Weight::from_ref_time(24_973_000 as u64)
+               // Standard Error: 2_000
+               .saturating_add(Weight::from_ref_time(4_265_000 as u64).saturating_mul(n as u64))
+               .saturating_add(T::DbWeight::get().reads(1 as u64))
+               .saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+               .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge BackForeign (r:1 w:1)
+     fn back_foreign() -> Weight {
+          Weight::from_ref_time(21_207_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(2 as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:1)
+     fn set_admin() -> Weight {
+          Weight::from_ref_time(17_052_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(1 as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:0 w:1)
+     // Storage: AssetsBridge BridgeModes (r:0 w:1)
+     // Storage: AssetsBridge GasLimits (r:0 w:1)
+     // Storage: AssetsBridge Emergencies (r:0 w:1)
+     // Storage: AssetsBridge NetBridged (r:0 w:1)
+     fn force_unregister() -> Weight {
+          Weight::from_ref_time(27_386_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(3 as u64))
+               .saturating_add(T::DbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn deposit_to() -> Weight {
+          Weight::from_ref_time(94_310_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(15 as u64))
+               .saturating_add(T::DbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge EvmAccounts (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn withdraw_to() -> Weight {
+          Weight::from_ref_time(91_527_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(16 as u64))
+               .saturating_add(T::DbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Erc20Code (r:0 w:1)
+     fn set_erc20_code(c: u32) -> Weight {
+          Weight::from_ref_time(9_840_000 as u64)
+               // Standard Error: 0
+               .saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(c as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge Erc20Code (r:1 w:0)
+     // Storage: Assets Metadata (r:1 w:0)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: System Account (r:1 w:1)
+     // Storage: EVM AccountStorages (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:1)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: AssetsBridge NetBridged (r:0 w:1)
+     fn register_and_deploy() -> Weight {
+          Weight::from_ref_time(412_000_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(9 as u64))
+               .saturating_add(T::DbWeight::get().writes(7 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: Assets Metadata (r:1 w:0)
+     // Storage: AssetsBridge Decimals (r:0 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: AssetsBridge NetBridged (r:0 w:1)
+     fn register_with_decimals() -> Weight {
+          Weight::from_ref_time(24_980_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(6 as u64))
+               .saturating_add(T::DbWeight::get().writes(5 as u64))
+     }
+     // Storage: AssetsBridge EvmAccounts (r:1 w:1)
+     // Storage: AssetsBridge SubAccounts (r:1 w:1)
+     // Storage: System BlockHash (r:1 w:0)
+     // Storage: EthereumChainId ChainId (r:1 w:0)
+     // Storage: System Account (r:2 w:2)
+     fn claim_account_eip712() -> Weight {
+          Weight::from_ref_time(121_806_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(6 as u64))
+               .saturating_add(T::DbWeight::get().writes(4 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:0 w:1)
+     fn set_volume_limit() -> Weight {
+          Weight::from_ref_time(21_320_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(1 as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge SubAccounts (r:1 w:0)
+     // Storage: AssetsBridge WithdrawNonces (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn withdraw_signed() -> Weight {
+          Weight::from_ref_time(138_507_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(16 as u64))
+               .saturating_add(T::DbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: Assets Metadata (r:1 w:0)
+     // Storage: AssetsBridge Decimals (r:0 w:1)
+     // Storage: AssetsBridge BridgeModes (r:0 w:1)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     fn register_lock_release() -> Weight {
+          Weight::from_ref_time(26_118_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(6 as u64))
+               .saturating_add(T::DbWeight::get().writes(5 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge GasLimits (r:0 w:1)
+     fn set_gas_limit() -> Weight {
+          Weight::from_ref_time(21_947_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(2 as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Relayer (r:0 w:1)
+     fn set_relayer() -> Weight {
+          Weight::from_ref_time(19_874_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(1 as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Relayer (r:1 w:0)
+     // Storage: AssetsBridge PendingBackForeign (r:1 w:1)
+     // Storage: AssetsBridge BackForeignExpiries (r:1 w:1)
+     fn confirm_back_foreign() -> Weight {
+          Weight::from_ref_time(22_613_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(3 as u64))
+               .saturating_add(T::DbWeight::get().writes(2 as u64))
+     }
+     // Storage: AssetsBridge Relayer (r:1 w:0)
+     // Storage: AssetsBridge PendingBackForeign (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BackForeignExpiries (r:1 w:1)
+     fn refund_back_foreign() -> Weight {
+          Weight::from_ref_time(48_902_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(8 as u64))
+               .saturating_add(T::DbWeight::get().writes(6 as u64))
+     }
+     // Storage: AssetsBridge PendingBackForeign (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BackForeignExpiries (r:1 w:1)
+     fn reclaim_back_foreign() -> Weight {
+          Weight::from_ref_time(47_455_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(7 as u64))
+               .saturating_add(T::DbWeight::get().writes(6 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge ReserveLocations (r:0 w:1)
+     fn set_reserve_location() -> Weight {
+          Weight::from_ref_time(20_417_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(1 as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge DestFormats (r:0 w:1)
+     fn set_dest_format() -> Weight {
+          Weight::from_ref_time(19_862_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(1 as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge DestFormats (r:1 w:0)
+     // Storage: AssetsBridge BackForeign (r:1 w:0)
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge ReserveLocations (r:1 w:0)
+     // Storage: AssetsBridge BackForeignNonce (r:1 w:1)
+     // Storage: AssetsBridge PendingBackForeign (r:0 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     fn transfer_back_foreign() -> Weight {
+          Weight::from_ref_time(61_253_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(11 as u64))
+               .saturating_add(T::DbWeight::get().writes(7 as u64))
+     }
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:0)
+     // Storage: AssetsBridge Emergencies (r:1 w:1)
+     fn report_drift() -> Weight {
+          Weight::from_ref_time(24_518_000 as u64)
+               .saturating_add(T::DbWeight::get().reads(4 as u64))
+               .saturating_add(T::DbWeight::get().writes(1 as u64))
+     }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+     // Storage: AssetsBridge EvmAccounts (r:1 w:1)
+     // Storage: AssetsBridge SubAccounts (r:1 w:1)
+     // Storage: System Account (r:2 w:2)
+     fn claim_account() -> Weight {
+          Weight::from_ref_time(118_402_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(4 as u64))
+               .saturating_add(RocksDbWeight::get().writes(4 as u64))
+     }
+     // Storage: AssetsBridge EvmAccounts (r:1 w:1)
+     // Storage: AssetsBridge SubAccounts (r:1 w:1)
+     // Storage: System Account (r:2 w:2)
+     fn dissolve() -> Weight {
+          Weight::from_ref_time(41_130_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(4 as u64))
+               .saturating_add(RocksDbWeight::get().writes(4 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge EvmAccounts (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn deposit() -> Weight {
+          Weight::from_ref_time(96_745_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(16 as u64))
+               .saturating_add(RocksDbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge EvmAccounts (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn withdraw() -> Weight {
+          Weight::from_ref_time(90_318_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(16 as u64))
+               .saturating_add(RocksDbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge EvmAccounts (r:1 w:0)
+     // Storage: System Account (r:2 w:2)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BackForeignNonce (r:1 w:1)
+     // Storage: AssetsBridge PendingBackForeign (r:0 w:1)
+     // Storage: AssetsBridge ReserveLocations (r:1 w:0)
+     fn teleport() -> Weight {
+          Weight::from_ref_time(52_604_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(9 as u64))
+               .saturating_add(RocksDbWeight::get().writes(7 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: AssetsBridge NetBridged (r:0 w:1)
+     fn register() -> Weight {
+          Weight::from_ref_time(26_977_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(5 as u64))
+               .saturating_add(RocksDbWeight::get().writes(4 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge Emergencies (r:1 w:1)
+     fn pause(n: u32) -> Weight {
+          Weight::from_ref_time(24_518_000 as u64)
+               // Standard Error: 2_000
+               .saturating_add(Weight::from_ref_time(4_187_000 as u64).saturating_mul(n as u64))
+               .saturating_add(RocksDbWeight::get().reads(1 as u64))
+               .saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+               .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge Emergencies (r:1 w:1)
+     fn unpause(n: u32) -> Weight {
+          Weight::from_ref_time(24_973_000 as u64)
+               // Standard Error: 2_000
+               .saturating_add(Weight::from_ref_time(4_265_000 as u64).saturating_mul(n as u64))
+               .saturating_add(RocksDbWeight::get().reads(1 as u64))
+               .saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+               .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge BackForeign (r:1 w:1)
+     fn back_foreign() -> Weight {
+          Weight::from_ref_time(21_207_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(2 as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:1)
+     fn set_admin() -> Weight {
+          Weight::from_ref_time(17_052_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(1 as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:0 w:1)
+     // Storage: AssetsBridge BridgeModes (r:0 w:1)
+     // Storage: AssetsBridge GasLimits (r:0 w:1)
+     // Storage: AssetsBridge Emergencies (r:0 w:1)
+     // Storage: AssetsBridge NetBridged (r:0 w:1)
+     fn force_unregister() -> Weight {
+          Weight::from_ref_time(27_386_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(3 as u64))
+               .saturating_add(RocksDbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn deposit_to() -> Weight {
+          Weight::from_ref_time(94_310_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(15 as u64))
+               .saturating_add(RocksDbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge EvmAccounts (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn withdraw_to() -> Weight {
+          Weight::from_ref_time(91_527_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(16 as u64))
+               .saturating_add(RocksDbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Erc20Code (r:0 w:1)
+     fn set_erc20_code(c: u32) -> Weight {
+          Weight::from_ref_time(9_840_000 as u64)
+               // Standard Error: 0
+               .saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(c as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge Erc20Code (r:1 w:0)
+     // Storage: Assets Metadata (r:1 w:0)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: System Account (r:1 w:1)
+     // Storage: EVM AccountStorages (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:1)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: AssetsBridge NetBridged (r:0 w:1)
+     fn register_and_deploy() -> Weight {
+          Weight::from_ref_time(412_000_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(9 as u64))
+               .saturating_add(RocksDbWeight::get().writes(7 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: Assets Metadata (r:1 w:0)
+     // Storage: AssetsBridge Decimals (r:0 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: AssetsBridge NetBridged (r:0 w:1)
+     fn register_with_decimals() -> Weight {
+          Weight::from_ref_time(24_980_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(6 as u64))
+               .saturating_add(RocksDbWeight::get().writes(5 as u64))
+     }
+     // Storage: AssetsBridge EvmAccounts (r:1 w:1)
+     // Storage: AssetsBridge SubAccounts (r:1 w:1)
+     // Storage: System BlockHash (r:1 w:0)
+     // Storage: EthereumChainId ChainId (r:1 w:0)
+     // Storage: System Account (r:2 w:2)
+     fn claim_account_eip712() -> Weight {
+          Weight::from_ref_time(121_806_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(6 as u64))
+               .saturating_add(RocksDbWeight::get().writes(4 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:0 w:1)
+     fn set_volume_limit() -> Weight {
+          Weight::from_ref_time(21_320_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(1 as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge SubAccounts (r:1 w:0)
+     // Storage: AssetsBridge WithdrawNonces (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     // Storage: System Account (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge Decimals (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:1)
+     // Storage: AssetsBridge EscrowBalances (r:1 w:1)
+     // Storage: AssetsBridge GasLimits (r:1 w:0)
+     fn withdraw_signed() -> Weight {
+          Weight::from_ref_time(138_507_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(16 as u64))
+               .saturating_add(RocksDbWeight::get().writes(8 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:1)
+     // Storage: AssetsBridge AssetIds (r:1 w:1)
+     // Storage: Assets Metadata (r:1 w:0)
+     // Storage: AssetsBridge Decimals (r:0 w:1)
+     // Storage: AssetsBridge BridgeModes (r:0 w:1)
+     // Storage: AssetsBridge CounterForErc20s (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:0)
+     fn register_lock_release() -> Weight {
+          Weight::from_ref_time(26_118_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(6 as u64))
+               .saturating_add(RocksDbWeight::get().writes(5 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge GasLimits (r:0 w:1)
+     fn set_gas_limit() -> Weight {
+          Weight::from_ref_time(21_947_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(2 as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge Relayer (r:0 w:1)
+     fn set_relayer() -> Weight {
+          Weight::from_ref_time(19_874_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(1 as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Relayer (r:1 w:0)
+     // Storage: AssetsBridge PendingBackForeign (r:1 w:1)
+     // Storage: AssetsBridge BackForeignExpiries (r:1 w:1)
+     fn confirm_back_foreign() -> Weight {
+          Weight::from_ref_time(22_613_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(3 as u64))
+               .saturating_add(RocksDbWeight::get().writes(2 as u64))
+     }
+     // Storage: AssetsBridge Relayer (r:1 w:0)
+     // Storage: AssetsBridge PendingBackForeign (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BackForeignExpiries (r:1 w:1)
+     fn refund_back_foreign() -> Weight {
+          Weight::from_ref_time(48_902_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(8 as u64))
+               .saturating_add(RocksDbWeight::get().writes(6 as u64))
+     }
+     // Storage: AssetsBridge PendingBackForeign (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: AssetsBridge BackForeignExpiries (r:1 w:1)
+     fn reclaim_back_foreign() -> Weight {
+          Weight::from_ref_time(47_455_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(7 as u64))
+               .saturating_add(RocksDbWeight::get().writes(6 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge ReserveLocations (r:0 w:1)
+     fn set_reserve_location() -> Weight {
+          Weight::from_ref_time(20_417_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(1 as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge Admin (r:1 w:0)
+     // Storage: AssetsBridge DestFormats (r:0 w:1)
+     fn set_dest_format() -> Weight {
+          Weight::from_ref_time(19_862_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(1 as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+     // Storage: AssetsBridge DestFormats (r:1 w:0)
+     // Storage: AssetsBridge BackForeign (r:1 w:0)
+     // Storage: AssetsBridge Emergencies (r:1 w:0)
+     // Storage: AssetsBridge VolumeLimits (r:1 w:0)
+     // Storage: AssetsBridge TotalVolumes (r:1 w:1)
+     // Storage: AssetsBridge AccountVolumes (r:1 w:1)
+     // Storage: Assets Asset (r:1 w:1)
+     // Storage: Assets Account (r:1 w:1)
+     // Storage: AssetsBridge ReserveLocations (r:1 w:0)
+     // Storage: AssetsBridge BackForeignNonce (r:1 w:1)
+     // Storage: AssetsBridge PendingBackForeign (r:0 w:1)
+     // Storage: AssetsBridge FreeCallsUsed (r:1 w:1)
+     fn transfer_back_foreign() -> Weight {
+          Weight::from_ref_time(61_253_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(11 as u64))
+               .saturating_add(RocksDbWeight::get().writes(7 as u64))
+     }
+     // Storage: AssetsBridge Erc20s (r:1 w:0)
+     // Storage: AssetsBridge BridgeModes (r:1 w:0)
+     // Storage: AssetsBridge NetBridged (r:1 w:0)
+     // Storage: AssetsBridge Emergencies (r:1 w:1)
+     fn report_drift() -> Weight {
+          Weight::from_ref_time(24_518_000 as u64)
+               .saturating_add(RocksDbWeight::get().reads(4 as u64))
+               .saturating_add(RocksDbWeight::get().writes(1 as u64))
+     }
+}
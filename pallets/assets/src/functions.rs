@@ -63,6 +63,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         Asset::<T, I>::get(id).map(|x| x.supply)
     }
 
+    /// Get the ids of all the existing assets.
+    pub fn asset_ids() -> impl Iterator<Item = T::AssetId> {
+        Asset::<T, I>::iter_keys()
+    }
+
     pub(super) fn new_account(
         who: &T::AccountId,
         d: &mut AssetDetails<T::Balance, T::AccountId, DepositBalanceOf<T, I>>,
@@ -654,6 +659,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
                 is_frozen: false,
             },
         );
+        T::CallbackHandle::created(id, &owner);
         Self::deposit_event(Event::ForceCreated { asset_id: id, owner });
         Ok(())
     }
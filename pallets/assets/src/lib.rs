@@ -244,6 +244,9 @@ pub mod pallet {
         /// Additional data to be stored with an account's asset balance.
         type Extra: Member + Parameter + Default + MaxEncodedLen;
 
+        /// A hook called after an asset has been created.
+        type CallbackHandle: AssetsCallback<Self::AssetId, Self::AccountId>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -336,6 +339,7 @@ pub mod pallet {
                         is_frozen: false,
                     },
                 );
+                T::CallbackHandle::created(*id, owner);
             }
 
             for (id, name, symbol, decimals) in &self.metadata {
@@ -534,6 +538,7 @@ pub mod pallet {
                     is_frozen: false,
                 },
             );
+            T::CallbackHandle::created(id, &owner);
             Self::deposit_event(Event::Created { asset_id: id, creator: owner, owner: admin });
             Ok(())
         }
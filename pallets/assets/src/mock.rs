@@ -99,6 +99,7 @@ impl Config for Test {
     type Freezer = TestFreezer;
     type WeightInfo = ();
     type Extra = ();
+    type CallbackHandle = TestCallback;
 }
 
 use std::collections::HashMap;
@@ -110,6 +111,7 @@ pub enum Hook {
 parameter_types! {
        static Frozen: HashMap<(u32, u64), u64> = Default::default();
        static Hooks: Vec<Hook> = Default::default();
+       static Created: Vec<u32> = Default::default();
 }
 
 pub struct TestFreezer;
@@ -126,6 +128,16 @@ impl FrozenBalance<u32, u64, u64> for TestFreezer {
     }
 }
 
+pub struct TestCallback;
+impl AssetsCallback<u32, u64> for TestCallback {
+    fn created(asset: u32, _: &u64) {
+        Created::mutate(|v| v.push(asset));
+
+        // Sanity check: the created asset exists.
+        assert!(Assets::maybe_total_supply(asset).is_some());
+    }
+}
+
 pub(crate) fn set_frozen_balance(asset: u32, who: u64, amount: u64) {
     Frozen::mutate(|v| {
         v.insert((asset, who), amount);
@@ -146,6 +158,10 @@ pub(crate) fn take_hooks() -> Vec<Hook> {
     Hooks::take()
 }
 
+pub(crate) fn created() -> Vec<u32> {
+    Created::get().clone()
+}
+
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
     let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
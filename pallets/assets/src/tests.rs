@@ -297,6 +297,24 @@ fn force_cancel_approval_works() {
     });
 }
 
+#[test]
+fn creation_callback_should_work() {
+    new_test_ext().execute_with(|| {
+        // the genesis asset
+        assert_eq!(created(), vec![999]);
+
+        Balances::make_free_balance_be(&1, 100);
+        assert_ok!(Assets::create(RuntimeOrigin::signed(1), 0, 1, 1));
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, 1, true, 1));
+        assert_noop!(
+            Assets::force_create(RuntimeOrigin::root(), 1, 1, true, 1),
+            Error::<Test>::InUse
+        );
+        assert_eq!(created(), vec![999, 0, 1]);
+        assert_eq!(Assets::asset_ids().count(), 3);
+    });
+}
+
 #[test]
 fn lifecycle_should_work() {
     new_test_ext().execute_with(|| {
@@ -185,6 +185,16 @@ impl<AssetId, AccountId, Balance> FrozenBalance<AssetId, AccountId, Balance> for
     fn died(_: AssetId, _: &AccountId) {}
 }
 
+/// Trait for reacting to the lifecycle of the asset classes.
+pub trait AssetsCallback<AssetId, AccountId> {
+    /// Called after an asset has been created by `create`, `force_create` or the genesis config.
+    fn created(asset: AssetId, owner: &AccountId);
+}
+
+impl<AssetId, AccountId> AssetsCallback<AssetId, AccountId> for () {
+    fn created(_: AssetId, _: &AccountId) {}
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub(super) struct TransferFlags {
     /// The debited account must stay alive at the end of the operation; an error is returned if
@@ -59,7 +59,7 @@ use frame_support::{
     construct_runtime,
     dispatch::DispatchClass,
     parameter_types,
-    traits::{EitherOfDiverse, Get},
+    traits::{Contains, EitherOfDiverse, Get},
     weights::{constants::WEIGHT_PER_SECOND, ConstantMultiplier, Weight},
     PalletId,
 };
@@ -86,7 +86,9 @@ use sp_runtime::{
         AccountIdLookup, BlakeTwo256, Block as BlockT, DispatchInfoOf, Dispatchable,
         PostDispatchInfoOf, UniqueSaturatedInto,
     },
-    transaction_validity::{TransactionSource, TransactionValidity, TransactionValidityError},
+    transaction_validity::{
+        TransactionPriority, TransactionSource, TransactionValidity, TransactionValidityError,
+    },
     ApplyExtrinsicResult, FixedPointNumber, Permill, Perquintill,
 };
 use sp_std::prelude::*;
@@ -103,7 +105,8 @@ pub use psc_common as common;
 use psc_common::{
     impls::{DealWithFees, ToStakingPot},
     opaque, AccountId, AssetId, AuraId, Balance, BlockNumber, Hash, Header, Index, Signature,
-    AVERAGE_ON_INITIALIZE_RATIO, HOURS, MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO, SLOT_DURATION,
+    AVERAGE_ON_INITIALIZE_RATIO, DAYS, HOURS, MAXIMUM_BLOCK_WEIGHT, NORMAL_DISPATCH_RATIO,
+    SLOT_DURATION,
 };
 use weights::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight};
 use xcm_config::{DotLocation, XcmConfig, XcmOriginToTransactDispatchOrigin};
@@ -186,7 +189,7 @@ impl frame_system::Config for Runtime {
     type Version = Version;
     type PalletInfo = PalletInfo;
     type OnNewAccount = ();
-    type OnKilledAccount = ();
+    type OnKilledAccount = AssetsBridge;
     type AccountData = pallet_balances::AccountData<Balance>;
     type SystemWeightInfo = weights::frame_system::WeightInfo<Runtime>;
     type SS58Prefix = SS58Prefix;
@@ -293,6 +296,7 @@ impl pallet_assets::Config for Runtime {
     type Extra = ();
     type WeightInfo = weights::pallet_assets::WeightInfo<Runtime>;
     type AssetAccountDeposit = AssetAccountDeposit;
+    type CallbackHandle = AssetsBridge;
 }
 
 parameter_types! {
@@ -421,11 +425,49 @@ parameter_types! {
     // 0x1111111111111111111111111111111111111111
     pub EvmCaller: H160 = H160::from_slice(&[17u8;20][..]);
     pub ClaimBond: Balance = 10 * EXISTENTIAL_DEPOSIT;
+    pub const BridgeFreeCalls: u32 = 10;
+    pub const BridgeFreeCallsPeriod: BlockNumber = HOURS;
+    pub const BridgeMaxErc20CodeSize: u32 = 48 * 1024;
+    pub const BridgeUnsignedPriority: TransactionPriority = TransactionPriority::MAX / 2;
+    pub const BridgeBackForeignTimeout: BlockNumber = DAYS;
+    pub const BridgeBackForeignRetention: BlockNumber = 7 * DAYS;
+    pub const BridgeDriftCheckInterval: BlockNumber = HOURS;
 }
+/// The bridge admin or root.
+pub type BridgeAdminOrRoot =
+    EitherOfDiverse<EnsureRoot<AccountId>, pallet_assets_bridge::EnsureAdmin<Runtime>>;
+/// The bridge admin, root, or the executive body of the relay chain by xcm.
+pub type BridgeRegisterOrigin =
+    EitherOfDiverse<CollatorSelectionUpdateOrigin, pallet_assets_bridge::EnsureAdmin<Runtime>>;
+
 impl pallet_assets_bridge::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type EvmCaller = EvmCaller;
     type ClaimBond = ClaimBond;
+    type FreeCalls = BridgeFreeCalls;
+    type FreeCallsPeriod = BridgeFreeCallsPeriod;
+    type MaxErc20CodeSize = BridgeMaxErc20CodeSize;
+    type BackForeignTimeout = BridgeBackForeignTimeout;
+    type BackForeignRetention = BridgeBackForeignRetention;
+    type XcmSender = xcm_config::XcmRouter;
+    type AccountIdToMultiLocation = xcm_config::AccountIdToMultiLocation;
+    type AssetPrecompileAddress = precompiles::AssetPrecompileAddress;
+    type UnclaimedAddressMapping = HashedAddressMapping<BlakeTwo256>;
+    type UnsignedPriority = BridgeUnsignedPriority;
+    type DriftCheckInterval = BridgeDriftCheckInterval;
+    type RegisterOrigin = BridgeRegisterOrigin;
+    type PauseOrigin = BridgeAdminOrRoot;
+    type BackForeignOrigin = BridgeAdminOrRoot;
+    type AdminOrigin = EnsureRoot<AccountId>;
+    type WeightInfo = weights::pallet_assets_bridge::WeightInfo<Runtime>;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+    RuntimeCall: From<C>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = UncheckedExtrinsic;
 }
 
 impl pallet_ethereum_chain_id::Config for Runtime {}
@@ -443,10 +485,39 @@ pub const WEIGHT_PER_GAS: u64 = WEIGHT_PER_SECOND.ref_time() / GAS_PER_SECOND;
 parameter_types! {
     pub BlockGasLimit: U256
         = U256::from(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT.ref_time() / WEIGHT_PER_GAS);
-    pub PrecompilesValue: PscPrecompiles<Runtime> = PscPrecompiles::<_>::new();
+    pub PrecompilesValue: PscPrecompiles<Runtime, EvmDispatchFilter> = PscPrecompiles::<_, _>::new();
     pub WeightPerGas: Weight = Weight::from_ref_time(WEIGHT_PER_GAS);
 }
 
+/// The calls allowed to be dispatched by the evm accounts through the dispatch precompile.
+pub struct EvmDispatchFilter;
+impl Contains<RuntimeCall> for EvmDispatchFilter {
+    fn contains(call: &RuntimeCall) -> bool {
+        match call {
+            RuntimeCall::Assets(
+                pallet_assets::Call::transfer { .. } |
+                pallet_assets::Call::transfer_keep_alive { .. } |
+                pallet_assets::Call::approve_transfer { .. } |
+                pallet_assets::Call::cancel_approval { .. } |
+                pallet_assets::Call::transfer_approved { .. },
+            ) => true,
+            // the deposits and withdrawals call the erc20 contracts, which must not re-enter the
+            // evm from a precompile
+            RuntimeCall::AssetsBridge(
+                pallet_assets_bridge::Call::teleport { .. } |
+                pallet_assets_bridge::Call::transfer_back_foreign { .. },
+            ) => true,
+            // the nested calls are limited by the decode depth of the precompile
+            RuntimeCall::Utility(
+                pallet_utility::Call::batch { calls } |
+                pallet_utility::Call::batch_all { calls } |
+                pallet_utility::Call::force_batch { calls },
+            ) => calls.iter().all(Self::contains),
+            _ => false,
+        }
+    }
+}
+
 impl pallet_evm::Config for Runtime {
     type FeeCalculator = BaseFee;
     type WeightPerGas = WeightPerGas;
@@ -454,11 +525,11 @@ impl pallet_evm::Config for Runtime {
     type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
     type CallOrigin = EnsureAddressRoot<AccountId>;
     type WithdrawOrigin = EnsureAddressNever<AccountId>;
-    type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+    type AddressMapping = pallet_assets_bridge::ClaimedAddressMapping<Runtime>;
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
     type Runner = pallet_evm::runner::stack::Runner<Self>;
-    type PrecompilesType = PscPrecompiles<Runtime>;
+    type PrecompilesType = PscPrecompiles<Runtime, EvmDispatchFilter>;
     type PrecompilesValue = PrecompilesValue;
     type ChainId = EthereumChainId;
     type OnChargeTransaction = pallet_evm::EVMCurrencyAdapter<Balances, DealWithFees<Runtime>>;
@@ -498,7 +569,7 @@ impl pallet_base_fee::Config for Runtime {
 }
 
 impl pallet_hotfix_sufficients::Config for Runtime {
-    type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+    type AddressMapping = pallet_assets_bridge::ClaimedAddressMapping<Runtime>;
     type WeightInfo = pallet_hotfix_sufficients::weights::SubstrateWeight<Runtime>;
 }
 
@@ -546,7 +617,9 @@ construct_runtime!(
             EthereumChainId: pallet_ethereum_chain_id::{Pallet, Call, Storage, Config} = 50,
             EVM: pallet_evm::{Pallet, Config, Call, Storage, Event<T>} = 51,
             Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Config, Origin} = 52,
-            AssetsBridge: pallet_assets_bridge::{Pallet, Call, Storage, Config<T>, Event<T>} = 53,
+            AssetsBridge: pallet_assets_bridge::{
+                Pallet, Call, Storage, Config<T>, Event<T>, ValidateUnsigned,
+            } = 53,
             BaseFee: pallet_base_fee::{Pallet, Call, Storage, Config<T>, Event} = 54,
             HotfixSufficients: pallet_hotfix_sufficients::{Pallet, Call} = 55,
        }
@@ -664,13 +737,26 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
+/// The runtime migrations per release.
+pub type Migrations = (
+    pallet_assets_bridge::migrations::v1::MigrateToV1<Runtime>,
+    pallet_assets_bridge::migrations::v2::MigrateToV2<Runtime>,
+    pallet_assets_bridge::migrations::v3::MigrateToV3<Runtime>,
+    pallet_assets_bridge::migrations::v4::MigrateToV4<Runtime>,
+    pallet_assets_bridge::migrations::v5::MigrateToV5<Runtime>,
+    pallet_assets_bridge::migrations::v6::MigrateToV6<Runtime>,
+    pallet_assets_bridge::migrations::v7::MigrateToV7<Runtime>,
+);
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
     define_benchmarks!(
         [frame_system, SystemBench::<Runtime>]
         [pallet_assets, Assets]
+        [pallet_assets_bridge, AssetsBridge]
         [pallet_balances, Balances]
         [pallet_multisig, Multisig]
         [pallet_session, SessionBench::<Runtime>]
@@ -956,6 +1042,40 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_assets_bridge_rpc_runtime_api::AssetsBridgeApi<Block, AccountId, AssetId> for Runtime {
+        fn evm_account(account: AccountId) -> Option<H160> {
+            AssetsBridge::evm_accounts(account)
+        }
+
+        fn sub_account(evm_address: H160) -> Option<AccountId> {
+            AssetsBridge::sub_accounts(evm_address)
+        }
+
+        fn erc20(asset_id: AssetId) -> Option<H160> {
+            AssetsBridge::erc20s(asset_id)
+        }
+
+        fn asset_id(erc20: H160) -> Option<AssetId> {
+            AssetsBridge::asset_ids(erc20)
+        }
+
+        fn pause_flags(asset_id: AssetId) -> pallet_assets_bridge_rpc_runtime_api::PauseFlags {
+            AssetsBridge::emergencies(asset_id)
+        }
+
+        fn registrations() -> Vec<pallet_assets_bridge_rpc_runtime_api::AssetRegistration<AssetId>> {
+            AssetsBridge::registrations()
+        }
+
+        fn paused_assets() -> Vec<AssetId> {
+            AssetsBridge::paused_assets()
+        }
+
+        fn back_foreign_assets() -> Vec<AssetId> {
+            AssetsBridge::back_foreign_assets()
+        }
+    }
+
     impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
         fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
             ParachainSystem::collect_collation_info(header)
@@ -0,0 +1,155 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The assets-bridge precompile, let evm accounts withdraw and query their mappings.
+
+use frame_support::traits::Get;
+use pallet_assets_bridge::WeightInfo;
+use pallet_evm::{
+    Context, ExitError, ExitReason, Precompile, PrecompileFailure, PrecompileHandle,
+    PrecompileResult,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
+
+use super::utils::{
+    dispatch_revert, ensure_no_delegatecall, ensure_not_static, read_selector, record_weight,
+    revert, succeed, EvmDataReader, EvmDataWriter, EvmResult,
+};
+
+// bytes4(keccak256(bytes("withdraw(uint32,uint256)"))
+const SELECTOR_WITHDRAW: u32 = 0x0e19dc3a;
+// bytes4(keccak256(bytes("subAccountOf(address)"))
+const SELECTOR_SUB_ACCOUNT_OF: u32 = 0xc743c7bd;
+// bytes4(keccak256(bytes("evmAccountOf(bytes32)"))
+const SELECTOR_EVM_ACCOUNT_OF: u32 = 0xc3e4dab1;
+
+/// The precompile of `pallet_assets_bridge`.
+///
+/// - `withdraw(uint32 assetId, uint256 amount)`: burn the erc20 tokens of the caller and mint the
+///   assets to its mapped substrate account.
+/// - `subAccountOf(address account) returns (bytes32)`: the zero value if not mapped.
+/// - `evmAccountOf(bytes32 account) returns (address)`: the zero value if not mapped.
+pub struct AssetsBridgePrecompile<R>(PhantomData<R>);
+
+impl<R> Precompile for AssetsBridgePrecompile<R>
+where
+    R: pallet_assets_bridge::Config,
+    R::AccountId: From<[u8; 32]> + Into<[u8; 32]>,
+    R::AssetId: From<u32>,
+    R::Balance: From<u128>,
+    DispatchError: From<<<R as pallet_evm::Config>::Runner as pallet_evm::Runner<R>>::Error>,
+{
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        match read_selector(handle.input())? {
+            SELECTOR_WITHDRAW => Self::withdraw(handle),
+            SELECTOR_SUB_ACCOUNT_OF => Self::sub_account_of(handle),
+            SELECTOR_EVM_ACCOUNT_OF => Self::evm_account_of(handle),
+            _ => Err(revert("unknown selector")),
+        }
+    }
+}
+
+impl<R> AssetsBridgePrecompile<R>
+where
+    R: pallet_assets_bridge::Config,
+    R::AccountId: From<[u8; 32]> + Into<[u8; 32]>,
+    R::AssetId: From<u32>,
+    R::Balance: From<u128>,
+    DispatchError: From<<<R as pallet_evm::Config>::Runner as pallet_evm::Runner<R>>::Error>,
+{
+    /// The erc20 contract is called in the current evm execution by `handle.call` rather than a
+    /// nested `Runner::call`, so its state changes are reverted with the ones of the caller and
+    /// the used gas is metered by the evm.
+    fn withdraw(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        ensure_no_delegatecall(handle)?;
+        ensure_not_static(handle)?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(2)?;
+        let asset_id: R::AssetId = reader.read_u32()?.into();
+        let amount = reader.read_u128()?;
+
+        record_weight::<R>(handle, <R as pallet_assets_bridge::Config>::WeightInfo::withdraw())?;
+
+        let caller = handle.context().caller;
+        let (erc20, input, gas_limit) =
+            pallet_assets_bridge::Pallet::<R>::prepare_withdraw_from_evm(
+                caller,
+                asset_id,
+                amount.into(),
+            )
+            .map_err(dispatch_revert)?;
+        if gas_limit > handle.remaining_gas() {
+            return Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas });
+        }
+
+        let context = Context {
+            address: erc20,
+            caller: <R as pallet_assets_bridge::Config>::EvmCaller::get(),
+            apparent_value: U256::zero(),
+        };
+        let (reason, output) = handle.call(erc20, None, input, Some(gas_limit), false, &context);
+        if !matches!(reason, ExitReason::Succeed(_)) {
+            return Err(revert("erc20 call failed"));
+        }
+
+        pallet_assets_bridge::Pallet::<R>::settle_withdraw_from_evm(
+            caller,
+            asset_id,
+            amount.into(),
+            &output,
+        )
+        .map_err(dispatch_revert)?;
+
+        Ok(succeed(EvmDataWriter::new().write_bool(true).build()))
+    }
+
+    fn sub_account_of(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        Self::record_read(handle)?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(1)?;
+        let evm_account = reader.read_address()?;
+
+        let account = pallet_assets_bridge::Pallet::<R>::sub_accounts(evm_account)
+            .map(|account| {
+                let account: [u8; 32] = account.into();
+                H256::from(account)
+            })
+            .unwrap_or_default();
+
+        Ok(succeed(EvmDataWriter::new().write_h256(account).build()))
+    }
+
+    fn evm_account_of(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        Self::record_read(handle)?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(1)?;
+        let account = R::AccountId::from(reader.read_h256()?.to_fixed_bytes());
+
+        let evm_account =
+            pallet_assets_bridge::Pallet::<R>::evm_accounts(account).unwrap_or_else(H160::zero);
+
+        Ok(succeed(EvmDataWriter::new().write_address(evm_account).build()))
+    }
+
+    fn record_read(handle: &mut impl PrecompileHandle) -> EvmResult {
+        record_weight::<R>(handle, <R as frame_system::Config>::DbWeight::get().reads(1))
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The erc20 precompile set of `pallet_assets`, one precompile address for each asset.
+
+use frame_support::traits::{
+    fungibles::{
+        approvals::{Inspect as ApprovalsInspect, Mutate as ApprovalsMutate},
+        metadata::Inspect as MetadataInspect,
+        Transfer,
+    },
+    Get,
+};
+use frame_system::RawOrigin;
+use pallet_assets::WeightInfo;
+use pallet_evm::{AddressMapping, PrecompileHandle, PrecompileResult};
+use sp_core::{H160, U256};
+use sp_runtime::traits::{Convert, StaticLookup, Zero};
+use sp_std::marker::PhantomData;
+
+use super::utils::{
+    dispatch_revert, ensure_no_delegatecall, ensure_not_static, log_erc20, read_selector,
+    record_weight, revert, succeed, EvmDataReader, EvmDataWriter, EvmResult, APPROVAL_TOPIC,
+    SELECTOR_ALLOWANCE, SELECTOR_APPROVE, SELECTOR_BALANCE_OF, SELECTOR_DECIMALS, SELECTOR_NAME,
+    SELECTOR_SYMBOL, SELECTOR_TOTAL_SUPPLY, SELECTOR_TRANSFER, SELECTOR_TRANSFER_FROM,
+    TRANSFER_TOPIC,
+};
+
+/// The first 4 bytes of the asset precompile addresses, the address of `asset_id` is
+/// prefix ++ 12 zero bytes ++ asset_id.
+pub const ASSET_PRECOMPILE_ADDRESS_PREFIX: [u8; 4] = [255u8; 4];
+
+/// The asset id of the precompile `address`, or None if it's not an asset precompile address.
+pub fn asset_id_of(address: H160) -> Option<u32> {
+    let bytes = address.as_bytes();
+    if bytes[0..4] != ASSET_PRECOMPILE_ADDRESS_PREFIX || bytes[4..16].iter().any(|b| *b != 0) {
+        return None;
+    }
+
+    let mut asset_id = [0u8; 4];
+    asset_id.copy_from_slice(&bytes[16..20]);
+
+    Some(u32::from_be_bytes(asset_id))
+}
+
+/// The precompile address of `asset_id`, set with the dummy code on the registration of
+/// `pallet_assets_bridge`.
+pub struct AssetPrecompileAddress;
+
+impl Convert<u32, H160> for AssetPrecompileAddress {
+    fn convert(asset_id: u32) -> H160 {
+        let mut address = [0u8; 20];
+        address[0..4].copy_from_slice(&ASSET_PRECOMPILE_ADDRESS_PREFIX);
+        address[16..20].copy_from_slice(&asset_id.to_be_bytes());
+
+        H160(address)
+    }
+}
+
+/// The erc20 precompile of the `pallet_assets` assets.
+///
+/// The balances and approvals are the ones of `pallet_assets`, and the evm addresses are mapped
+/// into substrate accounts by `pallet_evm::Config::AddressMapping`.
+pub struct AssetsErc20Precompile<R>(PhantomData<R>);
+
+impl<R> AssetsErc20Precompile<R>
+where
+    R: pallet_assets::Config + pallet_evm::Config,
+    R::AssetId: From<u32>,
+    R::Balance: From<u128> + Into<u128>,
+{
+    /// Whether `address` is an asset precompile address.
+    /// Note: the existence of the asset is checked by `execute`, where the storage read is charged.
+    pub fn is_precompile(address: H160) -> bool {
+        asset_id_of(address).is_some()
+    }
+
+    pub fn execute(handle: &mut impl PrecompileHandle, asset_id: u32) -> PrecompileResult {
+        let asset_id: R::AssetId = asset_id.into();
+
+        Self::record_read(handle)?;
+        if pallet_assets::Pallet::<R>::maybe_total_supply(asset_id).is_none() {
+            return Err(revert("asset not found"));
+        }
+
+        match read_selector(handle.input())? {
+            SELECTOR_BALANCE_OF => Self::balance_of(handle, asset_id),
+            SELECTOR_TRANSFER => Self::transfer(handle, asset_id),
+            SELECTOR_APPROVE => Self::approve(handle, asset_id),
+            SELECTOR_TRANSFER_FROM => Self::transfer_from(handle, asset_id),
+            SELECTOR_ALLOWANCE => Self::allowance(handle, asset_id),
+            SELECTOR_TOTAL_SUPPLY => Self::total_supply(handle, asset_id),
+            SELECTOR_NAME => Self::name(handle, asset_id),
+            SELECTOR_SYMBOL => Self::symbol(handle, asset_id),
+            SELECTOR_DECIMALS => Self::decimals(handle, asset_id),
+            _ => Err(revert("unknown selector")),
+        }
+    }
+
+    fn balance_of(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        Self::record_read(handle)?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(1)?;
+        let owner = Self::account(reader.read_address()?);
+
+        let balance: u128 = pallet_assets::Pallet::<R>::balance(asset_id, owner).into();
+
+        Ok(succeed(EvmDataWriter::new().write_u256(balance.into()).build()))
+    }
+
+    fn transfer(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        ensure_no_delegatecall(handle)?;
+        ensure_not_static(handle)?;
+        record_weight::<R>(handle, <R as pallet_assets::Config>::WeightInfo::transfer())?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(2)?;
+        let to = reader.read_address()?;
+        let amount = reader.read_u128()?;
+        let from = handle.context().caller;
+
+        <pallet_assets::Pallet<R> as Transfer<R::AccountId>>::transfer(
+            asset_id,
+            &Self::account(from),
+            &Self::account(to),
+            amount.into(),
+            false,
+        )
+        .map_err(dispatch_revert)?;
+
+        log_erc20(handle, TRANSFER_TOPIC, from, to, amount.into())?;
+
+        Ok(succeed(EvmDataWriter::new().write_bool(true).build()))
+    }
+
+    /// Set the allowance to `amount`, the existing approval is cancelled first since the
+    /// approvals of `pallet_assets` are accumulated.
+    fn approve(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        ensure_no_delegatecall(handle)?;
+        ensure_not_static(handle)?;
+        record_weight::<R>(
+            handle,
+            <R as pallet_assets::Config>::WeightInfo::cancel_approval()
+                .saturating_add(<R as pallet_assets::Config>::WeightInfo::approve_transfer()),
+        )?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(2)?;
+        let spender = reader.read_address()?;
+        let amount = reader.read_u128()?;
+        let owner = handle.context().caller;
+
+        let owner_account = Self::account(owner);
+        let spender_account = Self::account(spender);
+
+        let allowance = <pallet_assets::Pallet<R> as ApprovalsInspect<R::AccountId>>::allowance(
+            asset_id,
+            &owner_account,
+            &spender_account,
+        );
+        if !allowance.is_zero() {
+            pallet_assets::Pallet::<R>::cancel_approval(
+                RawOrigin::Signed(owner_account.clone()).into(),
+                asset_id,
+                R::Lookup::unlookup(spender_account.clone()),
+            )
+            .map_err(dispatch_revert)?;
+        }
+
+        if amount != 0 {
+            <pallet_assets::Pallet<R> as ApprovalsMutate<R::AccountId>>::approve(
+                asset_id,
+                &owner_account,
+                &spender_account,
+                amount.into(),
+            )
+            .map_err(dispatch_revert)?;
+        }
+
+        log_erc20(handle, APPROVAL_TOPIC, owner, spender, amount.into())?;
+
+        Ok(succeed(EvmDataWriter::new().write_bool(true).build()))
+    }
+
+    fn transfer_from(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        ensure_no_delegatecall(handle)?;
+        ensure_not_static(handle)?;
+        record_weight::<R>(handle, <R as pallet_assets::Config>::WeightInfo::transfer_approved())?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(3)?;
+        let from = reader.read_address()?;
+        let to = reader.read_address()?;
+        let amount = reader.read_u128()?;
+        let spender = handle.context().caller;
+
+        <pallet_assets::Pallet<R> as ApprovalsMutate<R::AccountId>>::transfer_from(
+            asset_id,
+            &Self::account(from),
+            &Self::account(spender),
+            &Self::account(to),
+            amount.into(),
+        )
+        .map_err(dispatch_revert)?;
+
+        log_erc20(handle, TRANSFER_TOPIC, from, to, amount.into())?;
+
+        Ok(succeed(EvmDataWriter::new().write_bool(true).build()))
+    }
+
+    fn allowance(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        Self::record_read(handle)?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(2)?;
+        let owner = Self::account(reader.read_address()?);
+        let spender = Self::account(reader.read_address()?);
+
+        let allowance: u128 =
+            <pallet_assets::Pallet<R> as ApprovalsInspect<R::AccountId>>::allowance(
+                asset_id, &owner, &spender,
+            )
+            .into();
+
+        Ok(succeed(EvmDataWriter::new().write_u256(allowance.into()).build()))
+    }
+
+    fn total_supply(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        Self::record_read(handle)?;
+
+        let supply: u128 = pallet_assets::Pallet::<R>::total_supply(asset_id).into();
+
+        Ok(succeed(EvmDataWriter::new().write_u256(supply.into()).build()))
+    }
+
+    fn name(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        Self::record_read(handle)?;
+
+        let name = <pallet_assets::Pallet<R> as MetadataInspect<R::AccountId>>::name(asset_id);
+
+        Ok(succeed(EvmDataWriter::new().write_bytes(&name).build()))
+    }
+
+    fn symbol(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        Self::record_read(handle)?;
+
+        let symbol = <pallet_assets::Pallet<R> as MetadataInspect<R::AccountId>>::symbol(asset_id);
+
+        Ok(succeed(EvmDataWriter::new().write_bytes(&symbol).build()))
+    }
+
+    fn decimals(handle: &mut impl PrecompileHandle, asset_id: R::AssetId) -> PrecompileResult {
+        Self::record_read(handle)?;
+
+        let decimals =
+            <pallet_assets::Pallet<R> as MetadataInspect<R::AccountId>>::decimals(asset_id);
+
+        Ok(succeed(EvmDataWriter::new().write_u256(U256::from(decimals)).build()))
+    }
+
+    fn account(address: H160) -> R::AccountId {
+        <R as pallet_evm::Config>::AddressMapping::into_account_id(address)
+    }
+
+    fn record_read(handle: &mut impl PrecompileHandle) -> EvmResult {
+        record_weight::<R>(handle, <R as frame_system::Config>::DbWeight::get().reads(1))
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The dispatch precompile, let evm accounts dispatch the allowed substrate calls.
+
+use codec::{Decode, DecodeLimit};
+use frame_support::{
+    dispatch::{GetDispatchInfo, PostDispatchInfo},
+    traits::{Contains, Get},
+};
+use pallet_evm::{
+    AddressMapping, ExitError, GasWeightMapping, Precompile, PrecompileFailure, PrecompileHandle,
+    PrecompileResult,
+};
+use sp_runtime::traits::Dispatchable;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+use super::utils::{
+    dispatch_revert, ensure_no_delegatecall, ensure_not_static, record_weight, revert, succeed,
+};
+
+/// The max depth of the nested calls, e.g. `Utility::batch`.
+const MAX_DECODE_DEPTH: u32 = 8;
+
+/// The precompile dispatching the scale encoded `RuntimeCall` of the input.
+///
+/// Only the calls allowed by `F` can be dispatched. The origin is the substrate account of the
+/// caller mapped by `pallet_evm::Config::AddressMapping`, i.e. the claimed one in
+/// `pallet_assets_bridge::SubAccounts` if any. The declared weight of the call must be covered by
+/// the remaining gas, and the actual weight is charged after the dispatch. It can't be called by
+/// `DELEGATECALL` or `CALLCODE`, which would dispatch with the origin of another caller.
+pub struct DispatchPrecompile<R, F>(PhantomData<(R, F)>);
+
+impl<R, F> Precompile for DispatchPrecompile<R, F>
+where
+    R: pallet_assets_bridge::Config,
+    R::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
+    <R::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<R::AccountId>>,
+    F: Contains<R::RuntimeCall>,
+{
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        ensure_no_delegatecall(handle)?;
+        ensure_not_static(handle)?;
+        // the read of the address mapping
+        record_weight::<R>(handle, <R as frame_system::Config>::DbWeight::get().reads(1))?;
+
+        let call = R::RuntimeCall::decode_with_depth_limit(MAX_DECODE_DEPTH, &mut handle.input())
+            .map_err(|_| revert("decode failed"))?;
+        if !F::contains(&call) {
+            return Err(revert("call not allowed"));
+        }
+
+        let info = call.get_dispatch_info();
+        if <R as pallet_evm::Config>::GasWeightMapping::weight_to_gas(info.weight) >
+            handle.remaining_gas()
+        {
+            return Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas });
+        }
+
+        let origin =
+            <R as pallet_evm::Config>::AddressMapping::into_account_id(handle.context().caller);
+        match call.dispatch(Some(origin).into()) {
+            Ok(post_info) => {
+                record_weight::<R>(handle, post_info.actual_weight.unwrap_or(info.weight))?;
+
+                Ok(succeed(Vec::new()))
+            },
+            Err(e) => {
+                record_weight::<R>(handle, e.post_info.actual_weight.unwrap_or(info.weight))?;
+
+                Err(dispatch_revert(e.error))
+            },
+        }
+    }
+}
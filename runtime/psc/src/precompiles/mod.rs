@@ -14,6 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use codec::Decode;
+use frame_support::{
+    dispatch::{GetDispatchInfo, PostDispatchInfo},
+    traits::Contains,
+};
 use pallet_evm::{Precompile, PrecompileHandle, PrecompileResult, PrecompileSet};
 use pallet_evm_precompile_blake2::Blake2F;
 use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
@@ -21,25 +26,42 @@ use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
 use sp_core::H160;
+use sp_runtime::{traits::Dispatchable, DispatchError};
 use sp_std::marker::PhantomData;
 
+mod assets_bridge;
+mod assets_erc20;
+mod dispatch;
+mod native_erc20;
+mod utils;
+
+pub use assets_bridge::AssetsBridgePrecompile;
+pub use assets_erc20::{
+    asset_id_of, AssetPrecompileAddress, AssetsErc20Precompile, ASSET_PRECOMPILE_ADDRESS_PREFIX,
+};
+pub use dispatch::DispatchPrecompile;
+pub use native_erc20::NativeErc20Precompile;
+
 /// We include the nine Istanbul precompiles
 /// (https://github.com/ethereum/go-ethereum/blob/3c46f557/core/vm/contracts.go#L69)
 /// as well as a special precompile for dispatching Substrate extrinsics
-pub struct PscPrecompiles<R>(PhantomData<R>);
+/// and the PSC specific precompiles.
+///
+/// `F` is the filter of the calls allowed to be dispatched by the dispatch precompile.
+pub struct PscPrecompiles<R, F>(PhantomData<(R, F)>);
 
-impl<R> PscPrecompiles<R>
+impl<R, F> PscPrecompiles<R, F>
 where
     R: pallet_evm::Config,
 {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self(PhantomData::<R>)
+        Self(PhantomData::<(R, F)>)
     }
     /// Return all addresses that contain precompiles. This can be used to populate dummy code
     /// under the precompile.
     pub fn used_addresses() -> sp_std::vec::Vec<H160> {
-        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 1026]
+        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 1024, 1025, 1026, 2048, 2049]
             .into_iter()
             .map(hash)
             .collect()
@@ -50,9 +72,18 @@ where
 /// 0-1023: Ethereum Mainnet Precompiles
 /// 1024-2047 Precompiles that are not in Ethereum Mainnet but are neither PSC specific
 /// 2048-4095 PSC specific precompiles
-impl<R> PrecompileSet for PscPrecompiles<R>
+/// 0xFFFFFFFF00000000000000000000000000000000 + AssetId: the erc20 precompiles of the assets
+impl<R, F> PrecompileSet for PscPrecompiles<R, F>
 where
-    R: pallet_evm::Config,
+    R: pallet_assets_bridge::Config + pallet_balances::Config,
+    R::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
+    <R::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<R::AccountId>>,
+    R::AccountId: From<[u8; 32]> + Into<[u8; 32]>,
+    R::AssetId: From<u32>,
+    <R as pallet_assets::Config>::Balance: From<u128> + Into<u128>,
+    <R as pallet_balances::Config>::Balance: From<u128> + Into<u128>,
+    DispatchError: From<<<R as pallet_evm::Config>::Runner as pallet_evm::Runner<R>>::Error>,
+    F: Contains<R::RuntimeCall>,
 {
     fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
         match handle.code_address() {
@@ -68,13 +99,20 @@ where
             a if a == hash(9) => Some(Blake2F::execute(handle)),
             // Non-PSC specific nor Ethereum precompiles :
             a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
-            // a if a == hash(1025) => Some(Dispatch::<R>::execute(handle)),
+            a if a == hash(1025) => Some(DispatchPrecompile::<R, F>::execute(handle)),
             a if a == hash(1026) => Some(ECRecoverPublicKey::execute(handle)),
+            // PSC specific precompiles :
+            a if a == hash(2048) => Some(AssetsBridgePrecompile::<R>::execute(handle)),
+            a if a == hash(2049) => Some(NativeErc20Precompile::<R>::execute(handle)),
+            // The erc20 precompiles of the assets :
+            a if AssetsErc20Precompile::<R>::is_precompile(a) =>
+                asset_id_of(a).map(|asset_id| AssetsErc20Precompile::<R>::execute(handle, asset_id)),
             _ => None,
         }
     }
     fn is_precompile(&self, address: H160) -> bool {
-        Self::used_addresses().contains(&address)
+        Self::used_addresses().contains(&address) ||
+            AssetsErc20Precompile::<R>::is_precompile(address)
     }
 }
 
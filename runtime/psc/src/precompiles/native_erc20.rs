@@ -0,0 +1,205 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The erc20 precompile of the native currency, like WETH without wrapping.
+
+use frame_support::traits::{Currency, ExistenceRequirement, Get};
+use pallet_assets_bridge::NativeAllowances;
+use pallet_balances::WeightInfo;
+use pallet_evm::{AddressMapping, PrecompileHandle, PrecompileResult};
+use sp_core::{H160, U256};
+use sp_std::marker::PhantomData;
+
+use super::utils::{
+    dispatch_revert, ensure_no_delegatecall, ensure_not_static, log_erc20, read_selector,
+    record_weight, revert, succeed, EvmDataReader, EvmDataWriter, EvmResult, APPROVAL_TOPIC,
+    SELECTOR_ALLOWANCE, SELECTOR_APPROVE, SELECTOR_BALANCE_OF, SELECTOR_DECIMALS, SELECTOR_NAME,
+    SELECTOR_SYMBOL, SELECTOR_TOTAL_SUPPLY, SELECTOR_TRANSFER, SELECTOR_TRANSFER_FROM,
+    TRANSFER_TOPIC,
+};
+
+/// The name of the native currency.
+const NAME: &[u8] = b"Polkadot";
+/// The symbol of the native currency.
+const SYMBOL: &[u8] = b"DOT";
+/// The decimals of the native currency.
+const DECIMALS: u8 = 10;
+
+/// The erc20 precompile of the native currency.
+///
+/// The balance is the free balance of `pallet_balances` in the native decimals, and the evm
+/// addresses are mapped into substrate accounts by `pallet_evm::Config::AddressMapping`. The
+/// allowances are the `NativeAllowances` of `pallet_assets_bridge`.
+pub struct NativeErc20Precompile<R>(PhantomData<R>);
+
+impl<R> NativeErc20Precompile<R>
+where
+    R: pallet_assets_bridge::Config + pallet_balances::Config,
+    <R as pallet_balances::Config>::Balance: From<u128> + Into<u128>,
+{
+    pub fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        match read_selector(handle.input())? {
+            SELECTOR_BALANCE_OF => Self::balance_of(handle),
+            SELECTOR_TRANSFER => Self::transfer(handle),
+            SELECTOR_APPROVE => Self::approve(handle),
+            SELECTOR_TRANSFER_FROM => Self::transfer_from(handle),
+            SELECTOR_ALLOWANCE => Self::allowance(handle),
+            SELECTOR_TOTAL_SUPPLY => Self::total_supply(handle),
+            SELECTOR_NAME => Ok(succeed(EvmDataWriter::new().write_bytes(NAME).build())),
+            SELECTOR_SYMBOL => Ok(succeed(EvmDataWriter::new().write_bytes(SYMBOL).build())),
+            SELECTOR_DECIMALS =>
+                Ok(succeed(EvmDataWriter::new().write_u256(U256::from(DECIMALS)).build())),
+            _ => Err(revert("unknown selector")),
+        }
+    }
+
+    fn balance_of(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        Self::record_reads(handle, 1)?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(1)?;
+        let owner = Self::account(reader.read_address()?);
+
+        let balance: u128 = pallet_balances::Pallet::<R>::free_balance(&owner).into();
+
+        Ok(succeed(EvmDataWriter::new().write_u256(balance.into()).build()))
+    }
+
+    fn transfer(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        ensure_no_delegatecall(handle)?;
+        ensure_not_static(handle)?;
+        record_weight::<R>(handle, <R as pallet_balances::Config>::WeightInfo::transfer())?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(2)?;
+        let to = reader.read_address()?;
+        let amount = reader.read_u128()?;
+        let from = handle.context().caller;
+
+        Self::do_transfer(from, to, amount)?;
+
+        log_erc20(handle, TRANSFER_TOPIC, from, to, amount.into())?;
+
+        Ok(succeed(EvmDataWriter::new().write_bool(true).build()))
+    }
+
+    /// Set the allowance to `amount`, the amount exceeding `u128::MAX` is unlimited. The owner
+    /// account should be provided, its allowances are removed when it is reaped.
+    fn approve(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        ensure_no_delegatecall(handle)?;
+        ensure_not_static(handle)?;
+        record_weight::<R>(
+            handle,
+            <R as frame_system::Config>::DbWeight::get().reads_writes(1, 1),
+        )?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(2)?;
+        let spender = reader.read_address()?;
+        let amount = reader.read_u256()?;
+        let owner = handle.context().caller;
+        let owner_account = Self::account(owner);
+
+        let allowance = if amount > U256::from(u128::MAX) { u128::MAX } else { amount.low_u128() };
+        if allowance == 0 {
+            NativeAllowances::<R>::remove(&owner_account, spender);
+        } else {
+            if !pallet_assets_bridge::Pallet::<R>::can_hold_native_allowances(&owner_account) {
+                return Err(revert("the owner account should be provided"));
+            }
+            NativeAllowances::<R>::insert(&owner_account, spender, allowance);
+        }
+
+        log_erc20(handle, APPROVAL_TOPIC, owner, spender, amount)?;
+
+        Ok(succeed(EvmDataWriter::new().write_bool(true).build()))
+    }
+
+    fn transfer_from(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        ensure_no_delegatecall(handle)?;
+        ensure_not_static(handle)?;
+        record_weight::<R>(
+            handle,
+            <R as pallet_balances::Config>::WeightInfo::transfer()
+                .saturating_add(<R as frame_system::Config>::DbWeight::get().reads_writes(2, 1)),
+        )?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(3)?;
+        let from = reader.read_address()?;
+        let to = reader.read_address()?;
+        let amount = reader.read_u128()?;
+        let spender = handle.context().caller;
+
+        let from_account = Self::account(from);
+        NativeAllowances::<R>::try_mutate(&from_account, spender, |allowance| -> EvmResult {
+            if *allowance != u128::MAX {
+                *allowance = allowance
+                    .checked_sub(amount)
+                    .ok_or_else(|| revert("trying to spend more than allowed"))?;
+            }
+
+            Self::do_transfer(from, to, amount)
+        })?;
+        // Reaping `from` removed its allowances before the mutated one was written back.
+        if !pallet_assets_bridge::Pallet::<R>::can_hold_native_allowances(&from_account) {
+            NativeAllowances::<R>::remove(&from_account, spender);
+        }
+
+        log_erc20(handle, TRANSFER_TOPIC, from, to, amount.into())?;
+
+        Ok(succeed(EvmDataWriter::new().write_bool(true).build()))
+    }
+
+    fn allowance(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        Self::record_reads(handle, 1)?;
+
+        let mut reader = EvmDataReader::new_skip_selector(handle.input())?;
+        reader.expect_arguments(2)?;
+        let owner = reader.read_address()?;
+        let spender = reader.read_address()?;
+
+        let allowance = NativeAllowances::<R>::get(Self::account(owner), spender);
+
+        Ok(succeed(EvmDataWriter::new().write_u256(allowance.into()).build()))
+    }
+
+    fn total_supply(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        Self::record_reads(handle, 1)?;
+
+        let supply: u128 = pallet_balances::Pallet::<R>::total_issuance().into();
+
+        Ok(succeed(EvmDataWriter::new().write_u256(supply.into()).build()))
+    }
+
+    fn do_transfer(from: H160, to: H160, amount: u128) -> EvmResult {
+        <pallet_balances::Pallet<R> as Currency<R::AccountId>>::transfer(
+            &Self::account(from),
+            &Self::account(to),
+            amount.into(),
+            ExistenceRequirement::AllowDeath,
+        )
+        .map_err(dispatch_revert)
+    }
+
+    fn account(address: H160) -> R::AccountId {
+        <R as pallet_evm::Config>::AddressMapping::into_account_id(address)
+    }
+
+    fn record_reads(handle: &mut impl PrecompileHandle, reads: u64) -> EvmResult {
+        record_weight::<R>(handle, <R as frame_system::Config>::DbWeight::get().reads(reads))
+    }
+}
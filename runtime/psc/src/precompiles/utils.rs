@@ -0,0 +1,282 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Solidity ABI helpers shared by the PSC specific precompiles, over the abi codec of the
+//! assets bridge pallet.
+
+use frame_support::weights::Weight;
+use pallet_assets_bridge::abi::{self, ParamType, Token};
+use pallet_evm::{
+    ExitRevert, ExitSucceed, GasWeightMapping, PrecompileFailure, PrecompileHandle,
+    PrecompileOutput,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+/// The result of the precompile helpers.
+pub type EvmResult<T = ()> = Result<T, PrecompileFailure>;
+
+// bytes4(keccak256(bytes("balanceOf(address)"))
+pub const SELECTOR_BALANCE_OF: u32 = 0x70a08231;
+// bytes4(keccak256(bytes("transfer(address,uint256)"))
+pub const SELECTOR_TRANSFER: u32 = 0xa9059cbb;
+// bytes4(keccak256(bytes("approve(address,uint256)"))
+pub const SELECTOR_APPROVE: u32 = 0x095ea7b3;
+// bytes4(keccak256(bytes("transferFrom(address,address,uint256)"))
+pub const SELECTOR_TRANSFER_FROM: u32 = 0x23b872dd;
+// bytes4(keccak256(bytes("allowance(address,address)"))
+pub const SELECTOR_ALLOWANCE: u32 = 0xdd62ed3e;
+// bytes4(keccak256(bytes("totalSupply()"))
+pub const SELECTOR_TOTAL_SUPPLY: u32 = 0x18160ddd;
+// bytes4(keccak256(bytes("name()"))
+pub const SELECTOR_NAME: u32 = 0x06fdde03;
+// bytes4(keccak256(bytes("symbol()"))
+pub const SELECTOR_SYMBOL: u32 = 0x95d89b41;
+// bytes4(keccak256(bytes("decimals()"))
+pub const SELECTOR_DECIMALS: u32 = 0x313ce567;
+
+/// keccak256(bytes("Transfer(address,address,uint256)"))
+pub const TRANSFER_TOPIC: H256 = H256([
+    0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+    0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+]);
+/// keccak256(bytes("Approval(address,address,uint256)"))
+pub const APPROVAL_TOPIC: H256 = H256([
+    0x8c, 0x5b, 0xe1, 0xe5, 0xeb, 0xec, 0x7d, 0x5b, 0xd1, 0x4f, 0x71, 0x42, 0x7d, 0x1e, 0x84, 0xf3,
+    0xdd, 0x03, 0x14, 0xc0, 0xf7, 0xb2, 0x29, 0x1e, 0x5b, 0x20, 0x0a, 0xc8, 0xc7, 0xc3, 0xb9, 0x25,
+]);
+
+/// The gas of one `LOG` operation.
+const G_LOG: u64 = 375;
+/// The gas of one topic of the `LOG` operation.
+const G_LOG_TOPIC: u64 = 375;
+/// The gas of one byte of the `LOG` data.
+const G_LOG_DATA: u64 = 8;
+
+/// Exit with a revert reason encoded as `Error(string)`, the rest gas is returned.
+pub fn revert(text: &str) -> PrecompileFailure {
+    let mut output = abi::ERROR_STRING_SELECTOR.to_vec();
+    output.extend(abi::encode(&[Token::String(text.as_bytes().to_vec())]));
+
+    PrecompileFailure::Revert { exit_status: ExitRevert::Reverted, output }
+}
+
+/// Exit with the revert reason of a failed dispatch.
+pub fn dispatch_revert(e: DispatchError) -> PrecompileFailure {
+    revert(<&'static str>::from(e))
+}
+
+/// Exit successfully with the abi encoded `output`.
+pub fn succeed(output: Vec<u8>) -> PrecompileOutput {
+    PrecompileOutput { exit_status: ExitSucceed::Returned, output }
+}
+
+/// Read the 4-bytes function selector of the input.
+pub fn read_selector(input: &[u8]) -> EvmResult<u32> {
+    if input.len() < 4 {
+        return Err(revert("tried to parse selector out of bounds"));
+    }
+
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&input[0..4]);
+
+    Ok(u32::from_be_bytes(selector))
+}
+
+/// Fail if the precompile is called in a static context (`STATICCALL`).
+pub fn ensure_not_static(handle: &impl PrecompileHandle) -> EvmResult {
+    if handle.is_static() {
+        return Err(revert("can't call non-static function in static context"));
+    }
+
+    Ok(())
+}
+
+/// Fail if the precompile is called by `DELEGATECALL` or `CALLCODE`,
+/// the caller is not the owner of the state the precompile changes then.
+pub fn ensure_no_delegatecall(handle: &impl PrecompileHandle) -> EvmResult {
+    if handle.code_address() != handle.context().address {
+        return Err(revert("can't be called with DELEGATECALL or CALLCODE"));
+    }
+
+    Ok(())
+}
+
+/// Charge the gas of `weight` from the handle.
+pub fn record_weight<R: pallet_evm::Config>(
+    handle: &mut impl PrecompileHandle,
+    weight: Weight,
+) -> EvmResult {
+    handle.record_cost(R::GasWeightMapping::weight_to_gas(weight))?;
+
+    Ok(())
+}
+
+/// Charge the gas of one log with `topics` and `data`, then emit it.
+pub fn log(
+    handle: &mut impl PrecompileHandle,
+    address: H160,
+    topics: Vec<H256>,
+    data: Vec<u8>,
+) -> EvmResult {
+    let cost = G_LOG
+        .saturating_add(G_LOG_TOPIC.saturating_mul(topics.len() as u64))
+        .saturating_add(G_LOG_DATA.saturating_mul(data.len() as u64));
+    handle.record_cost(cost)?;
+    handle.log(address, topics, data)?;
+
+    Ok(())
+}
+
+/// Charge the gas of one erc20 `Transfer` or `Approval` log, then emit it.
+pub fn log_erc20(
+    handle: &mut impl PrecompileHandle,
+    topic: H256,
+    from: H160,
+    to: H160,
+    amount: U256,
+) -> EvmResult {
+    let address = handle.code_address();
+    log(
+        handle,
+        address,
+        sp_std::vec![topic, H256::from(from), H256::from(to)],
+        EvmDataWriter::new().write_u256(amount).build(),
+    )
+}
+
+/// Read the abi encoded arguments after the selector, word by word.
+pub struct EvmDataReader<'a> {
+    input: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> EvmDataReader<'a> {
+    /// Create a reader of the arguments, skipping the selector.
+    pub fn new_skip_selector(input: &'a [u8]) -> EvmResult<Self> {
+        if input.len() < 4 {
+            return Err(revert("tried to parse selector out of bounds"));
+        }
+
+        Ok(Self { input: &input[4..], cursor: 0 })
+    }
+
+    /// Fail if the input has less than `count` words.
+    pub fn expect_arguments(&self, count: usize) -> EvmResult {
+        if self.input.len() >= self.cursor + count * 32 {
+            Ok(())
+        } else {
+            Err(revert("input doesn't match expected length"))
+        }
+    }
+
+    fn read_word(&mut self) -> EvmResult<&'a [u8]> {
+        let word = self
+            .input
+            .get(self.cursor..self.cursor + 32)
+            .ok_or_else(|| revert("tried to parse word out of bounds"))?;
+        self.cursor += 32;
+
+        Ok(word)
+    }
+
+    /// Decode the next word as `param`, a static abi type.
+    fn read_token(&mut self, param: ParamType) -> EvmResult<Token> {
+        abi::decode(&[param], self.read_word()?)
+            .and_then(|mut tokens| tokens.pop())
+            .ok_or_else(|| revert("tried to parse invalid word"))
+    }
+
+    pub fn read_u256(&mut self) -> EvmResult<U256> {
+        match self.read_token(ParamType::Uint)? {
+            Token::Uint(value) => Ok(value),
+            _ => Err(revert("tried to parse invalid word")),
+        }
+    }
+
+    pub fn read_u128(&mut self) -> EvmResult<u128> {
+        let value = self.read_u256()?;
+        if value > U256::from(u128::MAX) {
+            return Err(revert("value is too large for uint128"));
+        }
+
+        Ok(value.low_u128())
+    }
+
+    pub fn read_u32(&mut self) -> EvmResult<u32> {
+        let value = self.read_u256()?;
+        if value > U256::from(u32::MAX) {
+            return Err(revert("value is too large for uint32"));
+        }
+
+        Ok(value.low_u32())
+    }
+
+    pub fn read_address(&mut self) -> EvmResult<H160> {
+        match self.read_token(ParamType::Address)? {
+            Token::Address(value) => Ok(value),
+            _ => Err(revert("tried to parse invalid word")),
+        }
+    }
+
+    pub fn read_h256(&mut self) -> EvmResult<H256> {
+        Ok(H256::from_slice(self.read_word()?))
+    }
+}
+
+/// Write the abi encoded return values, word by word.
+#[derive(Default)]
+pub struct EvmDataWriter {
+    data: Vec<u8>,
+}
+
+impl EvmDataWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(self) -> Vec<u8> {
+        self.data
+    }
+
+    fn write_token(mut self, token: Token) -> Self {
+        self.data.extend(abi::encode(&[token]));
+        self
+    }
+
+    pub fn write_u256(self, value: U256) -> Self {
+        self.write_token(Token::Uint(value))
+    }
+
+    pub fn write_bool(self, value: bool) -> Self {
+        self.write_token(Token::Bool(value))
+    }
+
+    pub fn write_address(self, value: H160) -> Self {
+        self.write_token(Token::Address(value))
+    }
+
+    pub fn write_h256(mut self, value: H256) -> Self {
+        self.data.extend_from_slice(value.as_bytes());
+        self
+    }
+
+    /// Write `bytes` or `string` as the only returned value: offset ++ length ++ padded data.
+    pub fn write_bytes(self, value: &[u8]) -> Self {
+        self.write_token(Token::Bytes(value.to_vec()))
+    }
+}
@@ -47,16 +47,18 @@ use sp_std::marker::PhantomData;
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_assets::WeightInfo for WeightInfo<T> {
      // Storage: Assets Asset (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:1)
      fn create() -> Weight {
           Weight::from_ref_time(30_406_000 as u64)
-               .saturating_add(T::DbWeight::get().reads(1 as u64))
-               .saturating_add(T::DbWeight::get().writes(1 as u64))
+               .saturating_add(T::DbWeight::get().reads(2 as u64))
+               .saturating_add(T::DbWeight::get().writes(2 as u64))
      }
      // Storage: Assets Asset (r:1 w:1)
+     // Storage: EVM AccountCodes (r:1 w:1)
      fn force_create() -> Weight {
           Weight::from_ref_time(18_225_000 as u64)
-               .saturating_add(T::DbWeight::get().reads(1 as u64))
-               .saturating_add(T::DbWeight::get().writes(1 as u64))
+               .saturating_add(T::DbWeight::get().reads(2 as u64))
+               .saturating_add(T::DbWeight::get().writes(2 as u64))
      }
      // Storage: Assets Asset (r:1 w:1)
      // Storage: Assets Account (r:5002 w:5001)
@@ -28,6 +28,7 @@ use psc_common::{
     impls::ToStakingPot,
     xcm_config::{DenyTeleportToRelayChain, DenyThenTry},
 };
+use sp_runtime::traits::Convert;
 use xcm::latest::prelude::*;
 use xcm_builder::{
     AccountId32Aliases, AllowKnownQueryResponses, AllowSubscriptionsFrom,
@@ -182,6 +183,15 @@ impl xcm_executor::Config for XcmConfig {
 /// Forms the basis for local origins sending/executing XCMs.
 pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
 
+/// Converts a local account into the same `AccountId32` location on the other chains, e.g. the
+/// beneficiary of the assets-bridge back foreign transfers on the reserve chains.
+pub struct AccountIdToMultiLocation;
+impl Convert<AccountId, MultiLocation> for AccountIdToMultiLocation {
+    fn convert(account: AccountId) -> MultiLocation {
+        X1(AccountId32 { network: NetworkId::Any, id: account.into() }).into()
+    }
+}
+
 /// The means for routing XCM messages which are not for local execution into the right message
 /// queues.
 pub type XcmRouter = (
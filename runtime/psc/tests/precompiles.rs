@@ -0,0 +1,377 @@
+// Copyright (C) 2022-2023 Polkadot Smart Chain (PSC).
+// This file is part of PSC.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use asset_test_utils::{ExtBuilder, RuntimeHelper};
+use codec::Encode;
+use frame_support::assert_ok;
+use hex_literal::hex;
+use pallet_evm::{AddressMapping, CallInfo, ExitReason, ExitSucceed, Runner};
+use psc_common::AccountId;
+use psc_runtime::{Assets, AssetsBridge, Balances, ExistentialDeposit, Runtime, RuntimeCall};
+use sp_core::{H160, H256, U256};
+
+const ASSET_ID: u32 = 1;
+const ALICE: H160 = H160([1u8; 20]);
+const BOB: H160 = H160([2u8; 20]);
+const FORWARDER: H160 = H160([3u8; 20]);
+const ERC20: H160 = H160([4u8; 20]);
+const DISPATCH: H160 = H160(hex!("0000000000000000000000000000000000000401"));
+const ASSETS_BRIDGE: H160 = H160(hex!("0000000000000000000000000000000000000800"));
+const NATIVE_ERC20: H160 = H160(hex!("0000000000000000000000000000000000000801"));
+
+const DELEGATECALL: u8 = 0xf4;
+const CALLCODE: u8 = 0xf2;
+
+/// The erc20 precompile address of `asset_id`.
+fn asset_precompile(asset_id: u32) -> H160 {
+    let mut address = [0u8; 20];
+    address[0..4].copy_from_slice(&[255u8; 4]);
+    address[16..20].copy_from_slice(&asset_id.to_be_bytes());
+    H160(address)
+}
+
+fn account(address: H160) -> AccountId {
+    <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+}
+
+/// The code forwarding the calldata to `target` by `opcode`(`DELEGATECALL` or `CALLCODE`)
+/// and returning whether the forwarded call succeeded.
+fn forwarder(target: H160, opcode: u8) -> Vec<u8> {
+    // CALLDATACOPY(0, 0, CALLDATASIZE), then push retSize, retOffset, argsSize, argsOffset
+    let mut code = hex!("36600060003760006000366000").to_vec();
+    if opcode != DELEGATECALL {
+        // value
+        code.extend_from_slice(&hex!("6000"));
+    }
+    code.push(0x73);
+    code.extend_from_slice(target.as_bytes());
+    // GAS, the call, MSTORE(0, success), RETURN(0, 32)
+    code.extend_from_slice(&[0x5a, opcode]);
+    code.extend_from_slice(&hex!("60005260206000f3"));
+    code
+}
+
+fn call_evm(source: H160, target: H160, input: Vec<u8>) -> CallInfo {
+    <Runtime as pallet_evm::Config>::Runner::call(
+        source,
+        target,
+        input,
+        U256::zero(),
+        10_000_000,
+        None,
+        None,
+        None,
+        Vec::new(),
+        false,
+        true,
+        <Runtime as pallet_evm::Config>::config(),
+    )
+    .expect("the evm call should be executed")
+}
+
+fn transfer_input(to: H160, amount: u128) -> Vec<u8> {
+    let mut input = hex!("a9059cbb").to_vec();
+    input.extend_from_slice(H256::from(to).as_bytes());
+    let mut amount_bytes = [0u8; 32];
+    U256::from(amount).to_big_endian(&mut amount_bytes);
+    input.extend_from_slice(&amount_bytes);
+    input
+}
+
+/// Execute `test` with the asset `ASSET_ID` created and 1000 of it minted to ALICE.
+fn with_asset(test: impl FnOnce()) {
+    ExtBuilder::<Runtime>::default().build().execute_with(|| {
+        assert_ok!(Assets::force_create(
+            RuntimeHelper::<Runtime>::root_origin(),
+            ASSET_ID,
+            account(ALICE).into(),
+            true,
+            1
+        ));
+        assert_ok!(Assets::mint(
+            RuntimeHelper::<Runtime>::origin_of(account(ALICE)),
+            ASSET_ID,
+            account(ALICE).into(),
+            1000
+        ));
+
+        test();
+    })
+}
+
+/// Execute `test` with ALICE endowed with 1000 existential deposits.
+fn with_balance(test: impl FnOnce()) {
+    ExtBuilder::<Runtime>::default()
+        .with_balances(vec![(account(ALICE), ExistentialDeposit::get() * 1000)])
+        .build()
+        .execute_with(test)
+}
+
+#[test]
+fn asset_erc20_transfer_should_work() {
+    with_asset(|| {
+        let info = call_evm(ALICE, asset_precompile(ASSET_ID), transfer_input(BOB, 100));
+
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+        assert_eq!(Assets::balance(ASSET_ID, account(ALICE)), 900);
+        assert_eq!(Assets::balance(ASSET_ID, account(BOB)), 100);
+    });
+}
+
+#[test]
+fn asset_erc20_should_reject_delegatecall() {
+    with_asset(|| {
+        for opcode in [DELEGATECALL, CALLCODE] {
+            pallet_evm::AccountCodes::<Runtime>::insert(
+                FORWARDER,
+                forwarder(asset_precompile(ASSET_ID), opcode),
+            );
+
+            // the precompile runs in the context of the forwarder, which is rejected
+            let info = call_evm(ALICE, FORWARDER, transfer_input(BOB, 100));
+
+            assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+            assert_eq!(U256::from_big_endian(&info.value), U256::zero());
+            assert_eq!(Assets::balance(ASSET_ID, account(ALICE)), 1000);
+            assert_eq!(Assets::balance(ASSET_ID, account(BOB)), 0);
+        }
+    });
+}
+
+#[test]
+fn native_erc20_transfer_should_work() {
+    with_balance(|| {
+        let amount = ExistentialDeposit::get() * 100;
+        let info = call_evm(ALICE, NATIVE_ERC20, transfer_input(BOB, amount));
+
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+        assert_eq!(Balances::free_balance(account(ALICE)), ExistentialDeposit::get() * 900);
+        assert_eq!(Balances::free_balance(account(BOB)), amount);
+    });
+}
+
+#[test]
+fn native_erc20_should_reject_delegatecall() {
+    with_balance(|| {
+        for opcode in [DELEGATECALL, CALLCODE] {
+            pallet_evm::AccountCodes::<Runtime>::insert(FORWARDER, forwarder(NATIVE_ERC20, opcode));
+
+            let amount = ExistentialDeposit::get() * 100;
+            let info = call_evm(ALICE, FORWARDER, transfer_input(BOB, amount));
+
+            assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+            assert_eq!(U256::from_big_endian(&info.value), U256::zero());
+            assert_eq!(Balances::free_balance(account(ALICE)), ExistentialDeposit::get() * 1000);
+            assert_eq!(Balances::free_balance(account(BOB)), 0);
+        }
+    });
+}
+
+#[test]
+fn native_erc20_allowances_should_be_removed_with_the_reaped_owner() {
+    with_balance(|| {
+        let amount = ExistentialDeposit::get() * 1000;
+        let mut approve = hex!("095ea7b3").to_vec();
+        approve.extend_from_slice(H256::from(BOB).as_bytes());
+        approve.extend_from_slice(&[255u8; 32]);
+        let info = call_evm(ALICE, NATIVE_ERC20, approve);
+
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+        assert_eq!(AssetsBridge::native_allowances(account(ALICE), BOB), u128::MAX);
+
+        // moving the whole balance reaps ALICE
+        let mut transfer_from = hex!("23b872dd").to_vec();
+        transfer_from.extend_from_slice(H256::from(ALICE).as_bytes());
+        transfer_from.extend_from_slice(&transfer_input(BOB, amount)[4..]);
+        let info = call_evm(BOB, NATIVE_ERC20, transfer_from);
+
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+        assert_eq!(Balances::free_balance(account(BOB)), amount);
+        assert_eq!(frame_system::Pallet::<Runtime>::providers(&account(ALICE)), 0);
+        assert_eq!(AssetsBridge::native_allowances(account(ALICE), BOB), 0);
+
+        // the owner should be provided to approve, not only kept by the nonce
+        let mut approve = hex!("095ea7b3").to_vec();
+        approve.extend_from_slice(H256::from(BOB).as_bytes());
+        approve.extend_from_slice(&[255u8; 32]);
+        let info = call_evm(ALICE, NATIVE_ERC20, approve);
+
+        assert!(matches!(info.exit_reason, ExitReason::Revert(_)));
+        assert_eq!(AssetsBridge::native_allowances(account(ALICE), BOB), 0);
+    });
+}
+
+fn asset_transfer_call(to: H160, amount: u128) -> Vec<u8> {
+    RuntimeCall::Assets(pallet_assets::Call::transfer {
+        id: ASSET_ID,
+        target: account(to).into(),
+        amount,
+    })
+    .encode()
+}
+
+#[test]
+fn dispatch_should_work() {
+    with_asset(|| {
+        let info = call_evm(ALICE, DISPATCH, asset_transfer_call(BOB, 100));
+
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+        assert_eq!(Assets::balance(ASSET_ID, account(ALICE)), 900);
+        assert_eq!(Assets::balance(ASSET_ID, account(BOB)), 100);
+    });
+}
+
+#[test]
+fn dispatch_should_reject_evm_reentry() {
+    with_asset(|| {
+        let call = RuntimeCall::AssetsBridge(pallet_assets_bridge::Call::deposit {
+            asset_id: ASSET_ID,
+            amount: 100,
+        });
+        let info = call_evm(ALICE, DISPATCH, call.encode());
+
+        assert!(matches!(info.exit_reason, ExitReason::Revert(_)));
+        assert_eq!(Assets::balance(ASSET_ID, account(ALICE)), 1000);
+    });
+}
+
+#[test]
+fn dispatch_should_reject_delegatecall() {
+    with_asset(|| {
+        for opcode in [DELEGATECALL, CALLCODE] {
+            pallet_evm::AccountCodes::<Runtime>::insert(FORWARDER, forwarder(DISPATCH, opcode));
+
+            let info = call_evm(ALICE, FORWARDER, asset_transfer_call(BOB, 100));
+
+            assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+            assert_eq!(U256::from_big_endian(&info.value), U256::zero());
+            assert_eq!(Assets::balance(ASSET_ID, account(ALICE)), 1000);
+            assert_eq!(Assets::balance(ASSET_ID, account(BOB)), 0);
+        }
+    });
+}
+
+fn bridge_withdraw_input(asset_id: u32, amount: u128) -> Vec<u8> {
+    let mut input = hex!("0e19dc3a").to_vec();
+    input.extend_from_slice(&[0u8; 28]);
+    input.extend_from_slice(&asset_id.to_be_bytes());
+    let mut amount_bytes = [0u8; 32];
+    U256::from(amount).to_big_endian(&mut amount_bytes);
+    input.extend_from_slice(&amount_bytes);
+    input
+}
+
+/// Execute `test` with the asset `ASSET_ID` registered to `ERC20` with the `code`, and ALICE
+/// claimed by its mapped account.
+fn with_bridged_asset(code: Vec<u8>, test: impl FnOnce()) {
+    with_asset(|| {
+        // registered while the erc20 answers `totalSupply()`
+        pallet_evm::AccountCodes::<Runtime>::insert(ERC20, hex!("600160005260206000f3").to_vec());
+        assert_ok!(AssetsBridge::register(
+            RuntimeHelper::<Runtime>::root_origin(),
+            ASSET_ID,
+            ERC20
+        ));
+        pallet_evm::AccountCodes::<Runtime>::insert(ERC20, code);
+        pallet_assets_bridge::SubAccounts::<Runtime>::insert(ALICE, account(ALICE));
+        pallet_assets_bridge::NetBridged::<Runtime>::insert(ASSET_ID, 100);
+
+        test();
+    })
+}
+
+#[test]
+fn assets_bridge_withdraw_should_work() {
+    // the erc20 returns true
+    with_bridged_asset(hex!("600160005260206000f3").to_vec(), || {
+        let info = call_evm(ALICE, ASSETS_BRIDGE, bridge_withdraw_input(ASSET_ID, 10));
+
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+        assert_eq!(Assets::balance(ASSET_ID, account(ALICE)), 1010);
+        assert_eq!(AssetsBridge::net_bridged(ASSET_ID), 90);
+    });
+}
+
+#[test]
+fn assets_bridge_withdraw_should_revert_with_the_erc20() {
+    // the erc20 reverts, its call is made in the current evm execution rather than a nested one
+    with_bridged_asset(hex!("60006000fd").to_vec(), || {
+        let info = call_evm(ALICE, ASSETS_BRIDGE, bridge_withdraw_input(ASSET_ID, 10));
+
+        assert!(matches!(info.exit_reason, ExitReason::Revert(_)));
+        assert_eq!(Assets::balance(ASSET_ID, account(ALICE)), 1000);
+        assert_eq!(AssetsBridge::net_bridged(ASSET_ID), 100);
+    });
+}
+
+#[test]
+fn assets_bridge_withdraw_should_reject_delegatecall() {
+    with_bridged_asset(hex!("600160005260206000f3").to_vec(), || {
+        for opcode in [DELEGATECALL, CALLCODE] {
+            pallet_evm::AccountCodes::<Runtime>::insert(
+                FORWARDER,
+                forwarder(ASSETS_BRIDGE, opcode),
+            );
+
+            let info = call_evm(ALICE, FORWARDER, bridge_withdraw_input(ASSET_ID, 10));
+
+            assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+            assert_eq!(U256::from_big_endian(&info.value), U256::zero());
+            assert_eq!(Assets::balance(ASSET_ID, account(ALICE)), 1000);
+            assert_eq!(AssetsBridge::net_bridged(ASSET_ID), 100);
+        }
+    });
+}
+
+#[test]
+fn asset_erc20_should_have_code_without_the_bridge() {
+    // the asset is not registered with the bridge, its precompile passes the `extcodesize` check
+    // of solidity: PUSH20 precompile EXTCODESIZE PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+    with_asset(|| {
+        let mut code = vec![0x73];
+        code.extend_from_slice(asset_precompile(ASSET_ID).as_bytes());
+        code.extend_from_slice(&hex!("3b60005260206000f3"));
+        pallet_evm::AccountCodes::<Runtime>::insert(FORWARDER, code);
+
+        let info = call_evm(ALICE, FORWARDER, Vec::new());
+
+        assert_eq!(AssetsBridge::erc20s(ASSET_ID), None);
+        assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+        assert_eq!(
+            U256::from_big_endian(&info.value),
+            U256::from(pallet_assets_bridge::PRECOMPILE_CODE.len())
+        );
+    });
+}
+
+#[test]
+fn asset_erc20_should_revert_for_missing_asset() {
+    with_asset(|| {
+        let info = call_evm(ALICE, asset_precompile(ASSET_ID + 1), transfer_input(BOB, 100));
+
+        assert!(matches!(info.exit_reason, ExitReason::Revert(_)));
+    });
+}
+
+#[test]
+fn register_should_set_asset_precompile_code() {
+    with_bridged_asset(hex!("600160005260206000f3").to_vec(), || {
+        assert_eq!(
+            pallet_evm::AccountCodes::<Runtime>::get(asset_precompile(ASSET_ID)),
+            pallet_assets_bridge::PRECOMPILE_CODE.to_vec()
+        );
+    });
+}